@@ -0,0 +1,40 @@
+use crate::enums::Generation;
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{HiddenAbilityBallError, HiddenAbilityGenerationError};
+
+/// Balls that could not hold a Pokemon with its hidden ability until Gen 7 made them inheritable together
+static NO_HIDDEN_ABILITY_BALLS: [&str; 8] = [
+    "fast-ball",
+    "level-ball",
+    "lure-ball",
+    "heavy-ball",
+    "love-ball",
+    "friend-ball",
+    "moon-ball",
+    "sport-ball",
+];
+
+/// Check that a hidden ability can be legally obtained in the target generation and ball.
+///
+/// Hidden abilities were introduced in Gen 5. If no generation was given, the latest rules apply.
+pub fn check_hidden_ability(ability: &str, generation: Option<&Generation>, ball: &str) -> Vec<SpecErrors> {
+    let mut errors: Vec<SpecErrors> = Vec::new();
+    let Some(generation) = generation else {
+        return errors;
+    };
+
+    if *generation < Generation::GEN5 {
+        errors.push(HiddenAbilityGenerationError {
+            ability: ability.to_string(),
+            generation: generation.clone().into(),
+        });
+    } else if *generation < Generation::GEN7 && NO_HIDDEN_ABILITY_BALLS.contains(&ball.to_lowercase().as_str()) {
+        errors.push(HiddenAbilityBallError {
+            ability: ability.to_string(),
+            ball: ball.to_string(),
+            generation: generation.clone().into(),
+        });
+    }
+
+    errors
+}
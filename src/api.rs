@@ -1,33 +1,375 @@
+use crate::api::ability::PokeAbility;
+use crate::api::egg_group::PokeEggGroup;
+use crate::api::evolution::PokeEvolution;
+use crate::api::form::PokeForm;
+use crate::api::item::PokeItem;
+use crate::api::move_data::{PokeMoveData, PokeMovePastValues};
+use crate::api::pokemon::PokemonData;
 use crate::api::pokemon_move::{MoveLearnMethod, PokeMove};
-use crate::console::err;
-use crate::enums::{Generation, LearnMethod};
-use std::collections::HashSet;
-use std::process::exit;
+use crate::api::response::ApiResponse;
+use crate::api::species::PokeSpecies;
+use crate::enums::{DamageClass, Generation, LearnMethod, PokeType};
+use chrono::Utc;
+use itertools::Itertools;
+use miette::{miette, Result};
+use num_traits::ToPrimitive;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use std::sync::OnceLock;
 
+pub mod ability;
+pub mod egg_group;
+pub mod evolution;
+pub mod form;
+pub mod item;
+pub mod move_data;
+pub mod pokemon;
 pub mod pokemon_move;
+pub mod response;
+pub mod species;
 
-pub fn get_poke_api_route(route: &str, use_base_uri: bool) -> serde_json::Value {
-    static BASE_URI: &str = "https://pokeapi.co/api/v2/";
+static BASE_URI: &str = "https://pokeapi.co/api/v2/";
 
-    let req = reqwest::blocking::get( if use_base_uri {String::from(BASE_URI)} else {String::from("")} + route);
-    if req.is_err() {
-        err("Failed to communicate with PokeAPI. Do you have an internet connection?");
-        exit(-1);
+/// Where a `Fetcher` gets PokeAPI's responses from
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FetchMode {
+    Online,  // Ask PokeAPI, only for changes if a response is already stored
+    Offline, // Only use stored responses, e.g. to derive cached data again without the network
+}
+
+/// Looks up the stored response for a route, if there is one
+type StoredResponses<'a> = Box<dyn Fn(&str) -> Option<ApiResponse> + 'a>;
+
+/// Fetches the routes that make up a resource, e.g. a pokemon, and collects every response so that the caller can
+/// store them alongside it. Stored responses are looked up with `stored`, so PokeAPI is only asked if they've changed.
+pub struct Fetcher<'a> {
+    mode: FetchMode,
+    stored: StoredResponses<'a>,
+    responses: RefCell<Vec<ApiResponse>>,
+}
+
+impl<'a> Fetcher<'a> {
+    pub fn new(mode: FetchMode, stored: impl Fn(&str) -> Option<ApiResponse> + 'a) -> Self {
+        Fetcher {
+            mode,
+            stored: Box::new(stored),
+            responses: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Take every response fetched so far, leaving none behind
+    pub fn take_responses(&self) -> Vec<ApiResponse> {
+        self.responses.take()
+    }
+
+    /// Fetch a route, or a full URL within PokeAPI. Returns `None` if PokeAPI has no such resource.
+    /// Offline, routes without a stored response are an error, as there's no telling whether PokeAPI has them.
+    fn fetch(&self, url: &str) -> Result<Option<serde_json::Value>> {
+        let route = url.strip_prefix(BASE_URI).unwrap_or(url);
+        let stored = (self.stored)(route);
+
+        if self.mode == FetchMode::Offline {
+            let stored = stored.ok_or(miette!(
+                help = "Fetch it while online first, e.g. with `cache refresh`",
+                "No response from PokeAPI is stored for '{}'",
+                route
+            ))?;
+            let body = stored.body.clone();
+            self.responses.borrow_mut().push(stored);
+            return Ok(Some(body));
+        }
+
+        let mut request = client().get(String::from(BASE_URI) + route);
+        if let Some(stored) = &stored {
+            if let Some(etag) = &stored.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &stored.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let req = request
+            .send()
+            .map_err(|_| miette!("Failed to communicate with PokeAPI. Do you have an internet connection?"))?;
+
+        if req.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if req.status() == StatusCode::NOT_MODIFIED
+            && let Some(stored) = stored
+        {
+            // PokeAPI confirmed the stored response is still current
+            let body = stored.body.clone();
+            self.responses.borrow_mut().push(ApiResponse { fetched_at: Utc::now().timestamp(), ..stored });
+            return Ok(Some(body));
+        }
+
+        let header = |name| req.headers().get(name).and_then(|value: &HeaderValue| value.to_str().ok()).map(String::from);
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        let body = req.json::<serde_json::Value>().map_err(|_| {
+            miette!("Failed to parse JSON from PokeAPI's response. Is the endpoint correct? {}{}", BASE_URI, route)
+        })?;
+
+        self.responses.borrow_mut().push(ApiResponse {
+            route: String::from(route),
+            body: body.clone(),
+            etag,
+            last_modified,
+            fetched_at: Utc::now().timestamp(),
+        });
+        Ok(Some(body))
+    }
+
+    /// Fetch a route, or a full URL within PokeAPI, that must exist
+    pub fn get(&self, url: &str) -> Result<serde_json::Value> {
+        self.fetch(url)?
+            .ok_or(miette!("PokeAPI has no resource at {}. Is the endpoint correct?", url))
+    }
+
+    /// Like `get`, but returns `None` when PokeAPI has no such resource
+    pub fn get_if_exists(&self, url: &str) -> Result<Option<serde_json::Value>> {
+        self.fetch(url)
+    }
+}
+
+/// A client shared between requests, so connections to PokeAPI get reused
+fn client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(Client::new)
+}
+
+/// Fetch a pokemon by name. If there is no such pokemon, fall back to the default form of the species by that name,
+/// as some species (e.g. `urshifu`) only exist in PokeAPI as their forms. Returns `None` if neither exists.
+pub fn api_get_pokemon_or_default_form(fetcher: &Fetcher, name: &str) -> Result<Option<serde_json::Value>> {
+    if let Some(pokemon_json) = fetcher.get_if_exists((String::from("pokemon/") + name).as_str())? {
+        return Ok(Some(pokemon_json));
+    }
+
+    let Some(species_json) = fetcher.get_if_exists((String::from("pokemon-species/") + name).as_str())? else {
+        return Ok(None);
+    };
+    match species_json["varieties"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|variety| variety["is_default"].as_bool().unwrap_or(false))
+    {
+        Some(default_variety) => Ok(Some(fetcher.get(default_variety["pokemon"]["url"].as_str().unwrap())?)),
+        None => Ok(None),
+    }
+}
+
+/// Fetch everything the cache stores about a pokemon, falling back to the default form of a species by that name.
+/// Returns `None` if PokeAPI has neither.
+///
+/// Evolution chains and egg groups are shared between species, so they are only fetched if `fetch_chain` and
+/// `fetch_egg_group` say so, e.g. because they aren't cached yet.
+pub fn api_get_pokemon_data(
+    fetcher: &Fetcher,
+    name: &str,
+    fetch_chain: impl Fn(i32) -> bool,
+    fetch_egg_group: impl Fn(&str) -> bool,
+) -> Result<Option<PokemonData>> {
+    let Some(pokemon_json) = api_get_pokemon_or_default_form(fetcher, name)? else {
+        return Ok(None);
+    };
+    let species = api_get_pokemon_species(fetcher, &pokemon_json)?;
+    let evolution_chain = match species.evolution_chain {
+        Some(chain) if fetch_chain(chain) => api_get_evolution_chain(fetcher, chain)?,
+        _ => Vec::new(),
+    };
+    let egg_groups = species
+        .egg_groups
+        .iter()
+        .filter(|egg_group| fetch_egg_group(egg_group))
+        .map(|egg_group| api_get_egg_group(fetcher, egg_group))
+        .collect::<Result<Vec<PokeEggGroup>>>()?;
+
+    Ok(Some(PokemonData {
+        evolution_chain,
+        egg_groups,
+        form: api_get_pokemon_form(fetcher, &pokemon_json)?,
+        moves: api_get_pokemon_moves(&pokemon_json),
+        abilities: api_get_pokemon_abilities(&pokemon_json),
+        types: api_get_pokemon_types(&pokemon_json),
+        base_stats: api_get_pokemon_base_stats(&pokemon_json),
+        species,
+        responses: fetcher.take_responses(),
+    }))
+}
+
+/// Walk PokeAPI's paginated listing of every pokemon, including alternate forms
+pub fn api_get_pokemon_listing(fetcher: &Fetcher) -> Result<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    let mut page = fetcher.get("pokemon?limit=100&offset=0")?;
+    loop {
+        names.extend(
+            page["results"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|pokemon| String::from(pokemon["name"].as_str().unwrap())),
+        );
+        match page["next"].as_str() {
+            Some(next) => page = fetcher.get(next)?,
+            None => return Ok(names),
+        }
+    }
+}
+
+/// Fetch the name of every species introduced in a generation
+pub fn api_get_generation_species(fetcher: &Fetcher, generation: &Generation) -> Result<Vec<String>> {
+    let generation_json = fetcher.get(format!("generation/{}/", generation.to_i32().unwrap()).as_str())?;
+    Ok(generation_json["pokemon_species"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|species| String::from(species["name"].as_str().unwrap()))
+        .collect())
+}
+
+/// For a given Pokemon JSON object, work out which species it is a form of and when it was introduced
+pub fn api_get_pokemon_form(fetcher: &Fetcher, pokemon_json: &serde_json::Value) -> Result<PokeForm> {
+    // The version group of a pokemon's first form is the one it was introduced in
+    let generation = match pokemon_json["forms"].as_array().and_then(|forms| forms.first()) {
+        Some(form) => fetcher.get(form["url"].as_str().unwrap())?["version_group"]["name"]
+            .as_str()
+            .and_then(Generation::parse),
+        None => None,
+    };
+
+    Ok(PokeForm {
+        species: String::from(pokemon_json["species"]["name"].as_str().unwrap()),
+        pokemon: String::from(pokemon_json["name"].as_str().unwrap()),
+        is_default: pokemon_json["is_default"].as_bool().unwrap_or(true),
+        generation,
+    })
+}
+
+/// For a given Pokemon JSON object, fetch the species it is a form of
+pub fn api_get_pokemon_species(fetcher: &Fetcher, pokemon_json: &serde_json::Value) -> Result<PokeSpecies> {
+    let species_json = fetcher.get(pokemon_json["species"]["url"].as_str().unwrap())?;
+
+    Ok(PokeSpecies {
+        name: String::from(species_json["name"].as_str().unwrap()),
+        is_legendary: species_json["is_legendary"].as_bool().unwrap_or(false),
+        is_mythical: species_json["is_mythical"].as_bool().unwrap_or(false),
+        is_baby: species_json["is_baby"].as_bool().unwrap_or(false),
+        gender_rate: species_json["gender_rate"].as_i64().map(|rate| rate as i8),
+        egg_groups: species_json["egg_groups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|egg_group| String::from(egg_group["name"].as_str().unwrap()))
+            .collect(),
+        evolution_chain: species_json["evolution_chain"]["url"].as_str().and_then(route_id),
+        generation: species_json["generation"]["name"].as_str().and_then(Generation::parse),
+        varieties: species_json["varieties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .sorted_by_key(|variety| !variety["is_default"].as_bool().unwrap_or(false))
+            .map(|variety| String::from(variety["pokemon"]["name"].as_str().unwrap()))
+            .collect(),
+    })
+}
+
+/// Extract the trailing id of a PokeAPI resource URL, e.g. `67` from `.../evolution-chain/67/`
+fn route_id(url: &str) -> Option<i32> {
+    url.trim_end_matches('/').rsplit('/').next()?.parse().ok()
+}
+
+/// Fetch every link of an evolution chain, walking the chain from its first stage
+pub fn api_get_evolution_chain(fetcher: &Fetcher, chain: i32) -> Result<Vec<PokeEvolution>> {
+    let chain_json = fetcher.get(format!("evolution-chain/{chain}/").as_str())?;
+
+    let mut evolutions: Vec<PokeEvolution> = Vec::new();
+    let mut stages: Vec<(&serde_json::Value, Option<String>)> = vec![(&chain_json["chain"], None)];
+    while let Some((stage, evolves_from)) = stages.pop() {
+        let species = String::from(stage["species"]["name"].as_str().unwrap());
+        for next_stage in stage["evolves_to"].as_array().unwrap() {
+            stages.push((next_stage, Some(species.clone())));
+        }
+
+        // A stage may evolve differently between games. Only require a level if every way of evolving needs one.
+        let min_levels: Option<Vec<u8>> = stage["evolution_details"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|details| details["min_level"].as_u64().map(|level| level as u8))
+            .collect();
+        let min_level = min_levels.and_then(|levels| levels.into_iter().min());
+
+        evolutions.push(PokeEvolution { chain, species, evolves_from, min_level });
     }
-    let json = req.unwrap().json();
-    if json.is_err() {
-        err((String::from(
-            "Failed to parse JSON from PokeAPI's response. Is the endpoint correct? ",
-        ) + BASE_URI + route)
-            .as_str());
-        exit(-1);
+
+    Ok(evolutions)
+}
+
+/// Fetch an egg group and every species in it
+pub fn api_get_egg_group(fetcher: &Fetcher, egg_group: &str) -> Result<PokeEggGroup> {
+    let egg_group_json = fetcher.get((String::from("egg-group/") + egg_group).as_str())?;
+
+    Ok(PokeEggGroup {
+        name: String::from(egg_group_json["name"].as_str().unwrap()),
+        species: egg_group_json["pokemon_species"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|species| String::from(species["name"].as_str().unwrap()))
+            .collect(),
+    })
+}
+
+/// Fetch a move's details, along with the values it had before any later changes. Returns `None` if PokeAPI has no such move.
+pub fn api_get_move(fetcher: &Fetcher, pk_move: &str) -> Result<Option<PokeMoveData>> {
+    let Some(move_json) = fetcher.get_if_exists((String::from("move/") + pk_move).as_str())? else {
+        return Ok(None);
+    };
+
+    let mut past_values: Vec<PokeMovePastValues> = Vec::new();
+    for values in move_json["past_values"].as_array().unwrap() {
+        // Values from version groups we don't know the generation of can't be applied to any generation
+        let Some(generation) = values["version_group"]["name"].as_str().and_then(Generation::parse) else {
+            continue;
+        };
+        past_values.push(PokeMovePastValues {
+            generation,
+            move_type: values["type"]["name"].as_str().and_then(|move_type| PokeType::try_from(move_type).ok()),
+            power: values["power"].as_u64().map(|power| power as u8),
+            accuracy: values["accuracy"].as_u64().map(|accuracy| accuracy as u8),
+            pp: values["pp"].as_u64().map(|pp| pp as u8),
+        });
     }
-    let json: serde_json::Value = json.unwrap();
-    json
+
+    Ok(Some(PokeMoveData {
+        name: String::from(move_json["name"].as_str().unwrap()),
+        generation: move_json["generation"]["name"].as_str().and_then(Generation::parse),
+        move_type: move_json["type"]["name"].as_str().and_then(|move_type| PokeType::try_from(move_type).ok()),
+        damage_class: move_json["damage_class"]["name"]
+            .as_str()
+            .and_then(|damage_class| DamageClass::try_from(damage_class).ok()),
+        power: move_json["power"].as_u64().map(|power| power as u8),
+        accuracy: move_json["accuracy"].as_u64().map(|accuracy| accuracy as u8),
+        pp: move_json["pp"].as_u64().map(|pp| pp as u8),
+        priority: move_json["priority"].as_i64().unwrap_or(0) as i8,
+        past_values,
+    }))
 }
 
-pub fn api_get_pokemon(species: &str) -> serde_json::Value {
-    get_poke_api_route((String::from("pokemon/") + species).as_str(), true)
+/// Fetch the name of every pokemon that can learn a move by any method, in any generation
+pub fn api_get_move_learners(fetcher: &Fetcher, pk_move: &str) -> Result<HashSet<String>> {
+    let move_json = fetcher.get((String::from("move/") + pk_move).as_str())?;
+
+    Ok(move_json["learned_by_pokemon"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|pokemon| String::from(pokemon["name"].as_str().unwrap()))
+        .collect())
 }
 
 /// For a given Pokemon JSON object, extract a structured list of moves.
@@ -42,9 +384,13 @@ pub fn api_get_pokemon_moves(pokemon_json: &serde_json::Value) -> Vec<PokeMove>
 
         // Iterate through the Move Learning Methods JSON array
         for method in pokemon_move["version_group_details"].as_array().unwrap() {
+            // Skip any learn method PokeAPI adds in future, rather than failing to cache the pokemon at all
+            let Some(learn_method) = LearnMethod::from(method["move_learn_method"]["name"].as_str().unwrap()) else {
+                continue;
+            };
+
             methods.insert(MoveLearnMethod {
-                method: LearnMethod::from(method["move_learn_method"]["name"].as_str().unwrap())
-                    .unwrap(),
+                method: learn_method,
                 level_learned_at: Some(method["level_learned_at"].as_u64().unwrap() as u8),
                 generation: Generation::parse(
                     &method["version_group"]["name"]
@@ -71,26 +417,85 @@ pub fn api_get_pokemon_moves(pokemon_json: &serde_json::Value) -> Vec<PokeMove>
     moves
 }
 
-pub fn api_get_pokemon_abilities(pokemon_json: &serde_json::Value) -> Vec<String> {
-    let mut abilities: Vec<String> = Vec::new();
+/// For a given Pokemon JSON object, extract its abilities along with their slots and whether they are hidden.
+pub fn api_get_pokemon_abilities(pokemon_json: &serde_json::Value) -> Vec<PokeAbility> {
+    let mut abilities: Vec<PokeAbility> = Vec::new();
 
     for ability in pokemon_json["abilities"].as_array().unwrap() {
-        abilities.push(String::from(ability["ability"]["name"].as_str().unwrap()));
+        abilities.push(PokeAbility {
+            name: String::from(ability["ability"]["name"].as_str().unwrap()),
+            slot: ability["slot"].as_u64().unwrap() as u8,
+            is_hidden: ability["is_hidden"].as_bool().unwrap(),
+        });
     }
 
     abilities
 }
 
-pub fn api_get_balls() -> HashSet<String> {
+/// For a given Pokemon JSON object, extract its base stats, keyed by the stat names used in `StatSpread`
+pub fn api_get_pokemon_base_stats(pokemon_json: &serde_json::Value) -> HashMap<String, u16> {
+    let mut base_stats: HashMap<String, u16> = HashMap::new();
+
+    for stat in pokemon_json["stats"].as_array().unwrap() {
+        let name = match stat["stat"]["name"].as_str().unwrap() {
+            "hp" => "hp",
+            "attack" => "atk",
+            "defense" => "def",
+            "special-attack" => "spatk",
+            "special-defense" => "spdef",
+            "speed" => "spd",
+            _ => continue, // Ignore stats that only exist in battle, e.g. accuracy and evasion
+        };
+        base_stats.insert(name.to_string(), stat["base_stat"].as_u64().unwrap() as u16);
+    }
+
+    base_stats
+}
+
+/// For a given Pokemon JSON object, extract its types in slot order
+pub fn api_get_pokemon_types(pokemon_json: &serde_json::Value) -> Vec<PokeType> {
+    let mut types: Vec<(u64, PokeType)> = Vec::new();
+
+    for poke_type in pokemon_json["types"].as_array().unwrap() {
+        if let Ok(type_name) = PokeType::try_from(poke_type["type"]["name"].as_str().unwrap()) {
+            types.push((poke_type["slot"].as_u64().unwrap(), type_name));
+        }
+    }
+
+    types.sort_by_key(|(slot, _)| *slot);
+    types.into_iter().map(|(_, poke_type)| poke_type).collect()
+}
+
+/// Fetch an item and the generations it appears in. Returns `None` if PokeAPI has no such item.
+pub fn api_get_item(fetcher: &Fetcher, item: &str) -> Result<Option<PokeItem>> {
+    let Some(item_json) = fetcher.get_if_exists((String::from("item/") + item).as_str())? else {
+        return Ok(None);
+    };
+
+    let mut generations: HashSet<Generation> = HashSet::new();
+    for game_index in item_json["game_indices"].as_array().unwrap() {
+        if let Some(generation) = Generation::parse(game_index["generation"]["name"].as_str().unwrap()) {
+            generations.insert(generation);
+        }
+    }
+
+    Ok(Some(PokeItem {
+        name: String::from(item_json["name"].as_str().unwrap()),
+        category: String::from(item_json["category"]["name"].as_str().unwrap()),
+        generations,
+    }))
+}
+
+pub fn api_get_balls(fetcher: &Fetcher) -> Result<HashSet<String>> {
     static BALL_URI: &str = "item-pocket/3/";
-    let response = get_poke_api_route(BALL_URI, true);
+    let response = fetcher.get(BALL_URI)?;
 
     let mut balls: HashSet<String> = HashSet::new();
     for subroute in response["categories"].as_array().unwrap() {
-        let subroute_json = get_poke_api_route(subroute["url"].as_str().unwrap(), false);
+        let subroute_json = fetcher.get(subroute["url"].as_str().unwrap())?;
         for ball in subroute_json["items"].as_array().unwrap() {
             balls.insert(ball["name"].as_str().unwrap().to_string());
         }
     }
-    balls
+    Ok(balls)
 }
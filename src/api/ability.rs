@@ -0,0 +1,20 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct PokeAbility {
+    pub name: String,
+    pub slot: u8, // 1 and 2 are regular abilities, 3 is the hidden ability
+    pub is_hidden: bool,
+}
+
+impl Display for PokeAbility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (slot {}){}",
+            self.name,
+            self.slot,
+            if self.is_hidden { " (hidden)" } else { "" }
+        )
+    }
+}
@@ -0,0 +1,15 @@
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// An egg group and every species in it
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeEggGroup {
+    pub name: String,
+    pub species: Vec<String>,
+}
+
+impl Display for PokeEggGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} |{}|", self.name, self.species.iter().format(","))
+    }
+}
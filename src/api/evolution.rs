@@ -0,0 +1,26 @@
+use std::fmt::{Display, Formatter};
+
+/// A single link in an evolution chain, e.g. `pikachu` evolving from `pichu`
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeEvolution {
+    pub chain: i32,
+    pub species: String,
+    pub evolves_from: Option<String>, // `None` for the first stage of the chain
+    pub min_level: Option<u8>,        // `None` if the evolution doesn't need a minimum level, e.g. trade evolutions
+}
+
+impl Display for PokeEvolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.evolves_from {
+            Some(evolves_from) => write!(
+                f,
+                "{} -> {} @lvl: {} (chain {})",
+                evolves_from,
+                self.species,
+                self.min_level.unwrap_or(0),
+                self.chain
+            ),
+            None => write!(f, "{} (chain {})", self.species, self.chain),
+        }
+    }
+}
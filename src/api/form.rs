@@ -0,0 +1,24 @@
+use crate::enums::Generation;
+use std::fmt::{Display, Formatter};
+
+/// Links a pokemon (as PokeAPI calls a specific form, e.g. `raichu-alola`) to the species it is a form of
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeForm {
+    pub species: String,
+    pub pokemon: String,
+    pub is_default: bool,
+    pub generation: Option<Generation>, // The generation the form was introduced in, if PokeAPI knows
+}
+
+impl Display for PokeForm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} -> {}{} ({:?})",
+            self.species,
+            self.pokemon,
+            if self.is_default { " (default)" } else { "" },
+            self.generation
+        )
+    }
+}
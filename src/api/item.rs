@@ -0,0 +1,23 @@
+use crate::enums::Generation;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeItem {
+    pub name: String,
+    pub category: String,
+    pub generations: HashSet<Generation>, // Every generation the item appears in. Empty if PokeAPI doesn't know.
+}
+
+impl Display for PokeItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) |{}|",
+            self.name,
+            self.category,
+            self.generations.iter().map(|g| format!("{g:?}")).sorted().format(",")
+        )
+    }
+}
@@ -0,0 +1,75 @@
+use crate::enums::{DamageClass, Generation, PokeType};
+use std::fmt::{Display, Formatter};
+
+/// Values a move had before they were changed in a later generation
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeMovePastValues {
+    pub generation: Generation, // The first generation these values no longer apply to
+    pub move_type: Option<PokeType>,
+    pub power: Option<u8>,
+    pub accuracy: Option<u8>,
+    pub pp: Option<u8>,
+}
+
+/// Details of a move itself, independent of which pokemon learn it
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeMoveData {
+    pub name: String,
+    pub generation: Option<Generation>, // The generation the move was introduced in, if PokeAPI knows
+    pub move_type: Option<PokeType>,     // `None` for moves of types that aren't in the main games, e.g. Shadow
+    pub damage_class: Option<DamageClass>,
+    pub power: Option<u8>,    // `None` for moves that don't deal damage, or whose damage varies
+    pub accuracy: Option<u8>, // `None` for moves that never miss
+    pub pp: Option<u8>,
+    pub priority: i8,
+    pub past_values: Vec<PokeMovePastValues>,
+}
+
+impl PokeMoveData {
+    /// The move as it was in a given generation, undoing any changes made in later generations
+    pub fn in_generation(&self, generation: &Generation) -> PokeMoveData {
+        let mut past_values: Vec<&PokeMovePastValues> = self
+            .past_values
+            .iter()
+            .filter(|past_values| past_values.generation > *generation)
+            .collect();
+        past_values.sort_by_key(|past_values| past_values.generation.clone());
+
+        // Each value comes from the earliest change made after the generation, as later changes build on it
+        PokeMoveData {
+            move_type: past_values
+                .iter()
+                .find_map(|past_values| past_values.move_type)
+                .or(self.move_type),
+            power: past_values.iter().find_map(|past_values| past_values.power).or(self.power),
+            accuracy: past_values
+                .iter()
+                .find_map(|past_values| past_values.accuracy)
+                .or(self.accuracy),
+            pp: past_values.iter().find_map(|past_values| past_values.pp).or(self.pp),
+            past_values: Vec::new(),
+            ..self.clone()
+        }
+    }
+}
+
+fn format_value(value: Option<u8>) -> String {
+    value.map_or(String::from("-"), |value| value.to_string())
+}
+
+impl Display for PokeMoveData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({:?}) {} {} power: {} accuracy: {} pp: {} priority: {}",
+            self.name,
+            self.generation,
+            self.move_type.map_or(String::from("???"), |move_type| move_type.to_string()),
+            self.damage_class.map_or(String::from("???"), |damage_class| damage_class.to_string()),
+            format_value(self.power),
+            format_value(self.accuracy),
+            format_value(self.pp),
+            self.priority
+        )
+    }
+}
@@ -0,0 +1,40 @@
+use crate::api::ability::PokeAbility;
+use crate::api::egg_group::PokeEggGroup;
+use crate::api::evolution::PokeEvolution;
+use crate::api::form::PokeForm;
+use crate::api::pokemon_move::PokeMove;
+use crate::api::response::ApiResponse;
+use crate::api::species::PokeSpecies;
+use crate::enums::PokeType;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Everything the cache stores about a pokemon, as fetched from PokeAPI but not yet cached.
+///
+/// The evolution chain and egg groups are left empty if they were already cached, or are being fetched elsewhere.
+#[derive(Debug, Clone)]
+pub struct PokemonData {
+    pub species: PokeSpecies,
+    pub evolution_chain: Vec<PokeEvolution>,
+    pub egg_groups: Vec<PokeEggGroup>,
+    pub form: PokeForm,
+    pub moves: Vec<PokeMove>,
+    pub abilities: Vec<PokeAbility>,
+    pub types: Vec<PokeType>,
+    pub base_stats: HashMap<String, u16>,
+    pub responses: Vec<ApiResponse>, // Every response from PokeAPI it was built from, to be stored along with it
+}
+
+impl Display for PokemonData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} |{}| {} moves, {} abilities",
+            self.form.pokemon,
+            self.types.iter().format(","),
+            self.moves.len(),
+            self.abilities.len()
+        )
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::{Display, Formatter};
+
+/// A raw response from PokeAPI, kept so that normalized data can be derived from it again without the network
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiResponse {
+    pub route: String, // Relative to PokeAPI's base URI, e.g. `pokemon/pikachu` or `pokemon-species/25/`
+    pub body: serde_json::Value,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub fetched_at: i64, // Unix time
+}
+
+impl Display for ApiResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (etag: {}, last modified: {}, fetched at {})",
+            self.route,
+            self.etag.as_deref().unwrap_or("-"),
+            self.last_modified.as_deref().unwrap_or("-"),
+            self.fetched_at
+        )
+    }
+}
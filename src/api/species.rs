@@ -0,0 +1,32 @@
+use crate::enums::Generation;
+use std::fmt::{Display, Formatter};
+
+/// Species-level data, shared by every form of a species
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PokeSpecies {
+    pub name: String,
+    pub is_legendary: bool,
+    pub is_mythical: bool,
+    pub is_baby: bool,
+    pub gender_rate: Option<i8>, // Chance of being female in eighths, or -1 if genderless
+    pub egg_groups: Vec<String>,
+    pub evolution_chain: Option<i32>,
+    pub generation: Option<Generation>, // The generation the species was introduced in
+    pub varieties: Vec<String>, // Every pokemon (form) of the species, default first
+}
+
+impl Display for PokeSpecies {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut categories: Vec<&str> = Vec::new();
+        if self.is_legendary {
+            categories.push("legendary");
+        }
+        if self.is_mythical {
+            categories.push("mythical");
+        }
+        if self.is_baby {
+            categories.push("baby");
+        }
+        write!(f, "{} |{}| egg groups: {}", self.name, categories.join(","), self.egg_groups.join(","))
+    }
+}
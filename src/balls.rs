@@ -0,0 +1,119 @@
+use crate::api::species::PokeSpecies;
+use crate::enums::Generation;
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{BallGenerationError, IllegalBallError};
+
+/// The generation each ball was introduced in
+static BALL_GENERATIONS: [(&str, Generation); 34] = [
+    ("poke-ball", Generation::GEN1),
+    ("great-ball", Generation::GEN1),
+    ("ultra-ball", Generation::GEN1),
+    ("master-ball", Generation::GEN1),
+    ("safari-ball", Generation::GEN1),
+    ("fast-ball", Generation::GEN2),
+    ("level-ball", Generation::GEN2),
+    ("lure-ball", Generation::GEN2),
+    ("heavy-ball", Generation::GEN2),
+    ("love-ball", Generation::GEN2),
+    ("friend-ball", Generation::GEN2),
+    ("moon-ball", Generation::GEN2),
+    ("sport-ball", Generation::GEN2),
+    ("premier-ball", Generation::GEN3),
+    ("net-ball", Generation::GEN3),
+    ("dive-ball", Generation::GEN3),
+    ("nest-ball", Generation::GEN3),
+    ("repeat-ball", Generation::GEN3),
+    ("timer-ball", Generation::GEN3),
+    ("luxury-ball", Generation::GEN3),
+    ("dusk-ball", Generation::GEN4),
+    ("heal-ball", Generation::GEN4),
+    ("quick-ball", Generation::GEN4),
+    ("cherish-ball", Generation::GEN4),
+    ("park-ball", Generation::GEN4),
+    ("dream-ball", Generation::GEN5),
+    ("beast-ball", Generation::GEN7),
+    ("strange-ball", Generation::GEN8),
+    ("feather-ball", Generation::GEN8),
+    ("wing-ball", Generation::GEN8),
+    ("jet-ball", Generation::GEN8),
+    ("leaden-ball", Generation::GEN8),
+    ("gigaton-ball", Generation::GEN8),
+    ("origin-ball", Generation::GEN8),
+];
+
+/// Mythical Pokemon are only distributed through events and Pokemon GO
+static MYTHICAL_BALLS: [&str; 4] = ["poke-ball", "great-ball", "ultra-ball", "cherish-ball"];
+
+/// PokeAPI has no notion of Ultra Beasts, so they are listed here
+static ULTRA_BEASTS: [&str; 11] = [
+    "nihilego",
+    "buzzwole",
+    "pheromosa",
+    "xurkitree",
+    "celesteela",
+    "kartana",
+    "guzzlord",
+    "poipole",
+    "naganadel",
+    "stakataka",
+    "blacephalon",
+];
+
+/// Species that can only be obtained as a gift, and the ball they are given in
+static GIFT_BALLS: [(&str, &str); 4] = [
+    ("type-null", "poke-ball"),
+    ("cosmog", "poke-ball"),
+    ("poipole", "beast-ball"),
+    ("kubfu", "poke-ball"),
+];
+
+/// Check that a pokemon can legally be in a given ball.
+///
+/// Takes into account when the ball was introduced and the species' category (legendary, mythical, Ultra Beast,
+/// gift-only). If no generation was given, the latest rules apply. Whether a hidden ability is compatible with the ball
+/// is checked by `check_hidden_ability`. Species details may be missing for pokemon cached before they were tracked,
+/// in which case those checks are skipped.
+pub fn check_ball(
+    pokemon: &str,
+    species: Option<&PokeSpecies>,
+    ball: &str,
+    generation: Option<&Generation>,
+) -> Vec<SpecErrors> {
+    let mut errors: Vec<SpecErrors> = Vec::new();
+    let ball = ball.to_lowercase();
+    let species_name = species.map(|species| species.name.as_str()).unwrap_or(pokemon);
+    let illegal = |reason: &str| IllegalBallError {
+        species: pokemon.to_string(),
+        ball: ball.clone(),
+        reason: reason.to_string(),
+    };
+
+    if let Some(generation) = generation
+        && let Some((_, introduced)) = BALL_GENERATIONS.iter().find(|(name, _)| *name == ball)
+        && introduced > generation
+    {
+        errors.push(BallGenerationError {
+            ball: ball.clone(),
+            generation: generation.clone().into(),
+        });
+    }
+
+    if let Some((_, gift_ball)) = GIFT_BALLS.iter().find(|(gift, _)| *gift == species_name) {
+        if *gift_ball != ball {
+            errors.push(illegal(format!("it is only obtainable as a gift in a {gift_ball}").as_str()));
+        }
+        return errors;
+    }
+
+    if species.is_some_and(|species| species.is_mythical) && !MYTHICAL_BALLS.contains(&ball.as_str()) {
+        errors.push(illegal("mythical Pokemon are only distributed in a few balls"));
+    }
+
+    // Dream Balls could not be used on legendaries or Ultra Beasts until Dynamax Adventures in Gen 8
+    let is_legendary = species.is_some_and(|species| species.is_legendary) || ULTRA_BEASTS.contains(&species_name);
+    if is_legendary && ball == "dream-ball" && generation.is_some_and(|generation| *generation < Generation::GEN8) {
+        errors.push(illegal("legendaries and Ultra Beasts could not be caught in Dream Balls before Gen 8"));
+    }
+
+    errors
+}
@@ -0,0 +1,320 @@
+use crate::api::species::PokeSpecies;
+use crate::cache::{
+    fetch_cached_species_details, fetch_egg_group, fetch_evolution_chain, fetch_move_learners, fetch_move_methods,
+    get_and_cache_move_learners, get_and_cache_species_details,
+};
+use crate::enums::{Generation, LearnMethod};
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{
+    IncompatibleEggMovesError, NoFatherChainError, UncheckedFathersError, UnbreedableSpeciesError,
+};
+use itertools::Itertools;
+use miette::Result;
+use rusqlite::Connection;
+use std::collections::{BTreeSet, HashSet};
+
+/// Species in the Undiscovered egg group can't breed at all
+static UNDISCOVERED_EGG_GROUP: &str = "no-eggs";
+
+/// Ditto breeds with almost anything, but has no moves to pass down
+static DITTO_EGG_GROUP: &str = "ditto";
+
+/// Gender rates are given in eighths female
+const FEMALE_ONLY: i8 = 8;
+const MALE_ONLY: i8 = 0;
+const GENDERLESS: i8 = -1;
+
+/// How many uncached species a breeding check fetches from PokeAPI at most. Each one takes several requests.
+pub const MAX_FATHER_FETCHES: usize = 10;
+
+/// A father and the egg moves it needs from its own father, keyed to avoid walking the same chain twice
+type ChainStep = (String, BTreeSet<String>);
+
+/// Keeps track of how many more species a breeding check may fetch from PokeAPI, and how many it had to skip
+struct FatherFetches {
+    limit: usize,
+    remaining: usize,
+    skipped: usize,
+}
+
+impl FatherFetches {
+    fn new(limit: usize) -> Self {
+        FatherFetches { limit, remaining: limit, skipped: 0 }
+    }
+}
+
+/// Look up a species' details along with the id of its default form, fetching it from PokeAPI if it isn't cached
+/// and the check may still fetch. Species cached before breeding data was tracked are treated as unknown.
+fn breeding_details(conn: &Connection, species: &str, fetches: &mut FatherFetches) -> Result<Option<(PokeSpecies, i32)>> {
+    let details = match fetch_cached_species_details(conn, species)? {
+        Some(details) => Some(details),
+        None if fetches.remaining > 0 => {
+            fetches.remaining -= 1;
+            get_and_cache_species_details(conn, species)?
+        }
+        None => {
+            fetches.skipped += 1;
+            None
+        }
+    };
+    Ok(details.filter(|(details, _)| details.evolution_chain.is_some()))
+}
+
+/// Check whether a species learns a move by a method that lets it pass the move down when breeding in a generation.
+/// Returns `None` if it doesn't know the move at all, and `Some(true)` if it only knows it as an egg move itself.
+fn father_method(conn: &Connection, father_id: i32, pk_move: &str, generation: &Generation) -> Result<Option<bool>> {
+    let methods: Vec<LearnMethod> = fetch_move_methods(conn, father_id, pk_move)?
+        .into_iter()
+        .filter(|method| match method.method {
+            LearnMethod::Egg => method.generation == *generation,
+            _ => method.generation.can_transfer_to(generation),
+        })
+        .map(|method| method.method)
+        .collect();
+
+    Ok(if methods.iter().any(|method| *method != LearnMethod::Egg) {
+        Some(false)
+    } else if methods.contains(&LearnMethod::Egg) {
+        Some(true)
+    } else {
+        None
+    })
+}
+
+/// Work out which species' egg groups and gender decide how a species breeds.
+/// Babies can't breed, so their eggs come from the stage they evolve into, e.g. `pichu` from `pikachu`.
+fn breeding_species(conn: &Connection, species: &PokeSpecies, fetches: &mut FatherFetches) -> Result<PokeSpecies> {
+    if !species.is_baby {
+        return Ok(species.clone());
+    }
+
+    let evolutions = match species.evolution_chain {
+        Some(chain) => fetch_evolution_chain(conn, chain)?,
+        None => Vec::new(),
+    };
+    match evolutions
+        .iter()
+        .find(|evolution| evolution.evolves_from.as_ref() == Some(&species.name))
+    {
+        Some(evolution) => Ok(breeding_details(conn, &evolution.species, fetches)?
+            .map(|(details, _)| details)
+            .unwrap_or(species.clone())),
+        None => Ok(species.clone()),
+    }
+}
+
+/// Check whether a species can father an egg with a given mother
+fn can_father(father: &PokeSpecies, mother: &PokeSpecies) -> bool {
+    let can_breed = !father.is_baby
+        && !father
+            .egg_groups
+            .iter()
+            .any(|egg_group| egg_group == UNDISCOVERED_EGG_GROUP || egg_group == DITTO_EGG_GROUP);
+
+    // Genderless species only breed with Ditto, so they can only pass moves down their own line
+    match father.gender_rate {
+        Some(FEMALE_ONLY) => false,
+        Some(GENDERLESS) => can_breed && mother.gender_rate == Some(GENDERLESS),
+        _ => can_breed,
+    }
+}
+
+/// Find every species that could father an egg with a given mother while knowing all the given moves,
+/// from the cached egg groups and the learners of each move. Cached species come first.
+fn father_candidates(
+    conn: &Connection,
+    mother: &PokeSpecies,
+    moves: &BTreeSet<String>,
+    fetches: &FatherFetches,
+) -> Result<Vec<String>> {
+    // Species without females can only breed with Ditto, so their egg moves must come from their own line
+    let mut candidates: HashSet<String> = HashSet::new();
+    if matches!(mother.gender_rate, Some(MALE_ONLY) | Some(GENDERLESS)) {
+        if let Some(chain) = mother.evolution_chain {
+            candidates.extend(fetch_evolution_chain(conn, chain)?.into_iter().map(|evolution| evolution.species));
+        }
+    } else {
+        for egg_group in &mother.egg_groups {
+            candidates.extend(fetch_egg_group(conn, egg_group)?);
+        }
+    }
+
+    // PokeAPI lists learners by pokemon rather than species, so a species matches its forms too
+    for pk_move in moves {
+        // Every learner of a move is fetched with a single request, so they're only left alone if nothing may be fetched
+        let learners = if fetches.limit == 0 {
+            fetch_move_learners(conn, pk_move)?
+        } else {
+            get_and_cache_move_learners(conn, pk_move)?
+        };
+        candidates.retain(|species| {
+            learners
+                .iter()
+                .any(|learner| learner == species || learner.starts_with(format!("{species}-").as_str()))
+        });
+    }
+
+    // Try species that are already cached first, to avoid fetching more from PokeAPI than needed
+    let mut cached: Vec<String> = Vec::new();
+    let mut uncached: Vec<String> = Vec::new();
+    for species in candidates.into_iter().sorted() {
+        match fetch_cached_species_details(conn, &species)? {
+            Some(_) => cached.push(species),
+            None => uncached.push(species),
+        }
+    }
+    cached.extend(uncached);
+    Ok(cached)
+}
+
+/// Check whether some chain of fathers can pass every given move to a mother's egg at once.
+///
+/// A father may know a move by any method. Moves it only knows as egg moves must in turn come from its own father.
+fn has_father_chain(
+    conn: &Connection,
+    mother: &PokeSpecies,
+    moves: &BTreeSet<String>,
+    generation: &Generation,
+    visited: &mut HashSet<ChainStep>,
+    fetches: &mut FatherFetches,
+) -> Result<bool> {
+    for candidate in father_candidates(conn, mother, moves, fetches)? {
+        if !visited.insert((candidate.clone(), moves.clone())) {
+            continue;
+        }
+
+        let (father, father_id) = match breeding_details(conn, &candidate, fetches)? {
+            Some(details) => details,
+            None => continue,
+        };
+        if !can_father(&father, mother) {
+            continue;
+        }
+
+        let mut inherited: BTreeSet<String> = BTreeSet::new();
+        let mut knows_moves = true;
+        for pk_move in moves {
+            match father_method(conn, father_id, pk_move, generation)? {
+                Some(false) => {}
+                Some(true) => {
+                    inherited.insert(pk_move.clone());
+                }
+                None => {
+                    knows_moves = false;
+                    break;
+                }
+            }
+        }
+
+        if knows_moves
+            && (inherited.is_empty() || has_father_chain(conn, &father, &inherited, generation, visited, fetches)?)
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// The latest generation in which a species learns a move as an egg move
+fn latest_egg_generation(conn: &Connection, pokemon_id: i32, pk_move: &str) -> Result<Option<Generation>> {
+    Ok(fetch_move_methods(conn, pokemon_id, pk_move)?
+        .into_iter()
+        .filter(|method| method.method == LearnMethod::Egg)
+        .map(|method| method.generation)
+        .max())
+}
+
+/// Check that a species' egg moves can all be passed down to it by breeding in the target generation.
+///
+/// Each egg move needs a father that shares an egg group with the mother and knows the move.
+/// Before Gen 6 only the father passes egg moves down, so a single father must know every egg move at once.
+/// From Gen 6 the mother passes egg moves down too, so each egg move only needs a chain of its own.
+/// If no generation was given, each egg move is checked in the latest generation it is an egg move in.
+///
+/// Fathers are looked for among cached species first, and at most `fetch_limit` uncached species are fetched from
+/// PokeAPI. With a limit of 0, nothing is fetched at all. If no chain is found but some fathers couldn't be checked,
+/// that's reported rather than the egg moves being called illegal outright.
+pub fn check_egg_moves(
+    conn: &Connection,
+    species: &PokeSpecies,
+    pokemon_id: i32,
+    egg_moves: &[String],
+    generation: Option<&Generation>,
+    fetch_limit: usize,
+) -> Result<Vec<SpecErrors>> {
+    let mut errors: Vec<SpecErrors> = Vec::new();
+    let mut fetches = FatherFetches::new(fetch_limit);
+    let mother = breeding_species(conn, species, &mut fetches)?;
+
+    if mother.egg_groups.iter().any(|egg_group| egg_group == UNDISCOVERED_EGG_GROUP) {
+        errors.push(UnbreedableSpeciesError {
+            species: species.name.clone(),
+            reason: if species.is_baby && mother.name == species.name {
+                String::from("it is a baby that never evolves into a species that can breed")
+            } else {
+                format!("{} is in the Undiscovered egg group", mother.name)
+            },
+        });
+        return Ok(errors);
+    }
+
+    let mut unchained: Vec<String> = Vec::new();
+    for pk_move in egg_moves {
+        let move_generation = match generation {
+            Some(generation) => Some(generation.clone()),
+            None => latest_egg_generation(conn, pokemon_id, pk_move)?,
+        };
+        let Some(move_generation) = move_generation else {
+            continue;
+        };
+
+        let moves = BTreeSet::from([pk_move.clone()]);
+        fetches.skipped = 0;
+        if !has_father_chain(conn, &mother, &moves, &move_generation, &mut HashSet::new(), &mut fetches)? {
+            unchained.push(pk_move.clone());
+            errors.push(if fetches.skipped > 0 {
+                UncheckedFathersError {
+                    species: species.name.clone(),
+                    moves: pk_move.clone(),
+                    generation: move_generation.into(),
+                    unchecked: fetches.skipped.to_string(),
+                }
+            } else {
+                NoFatherChainError {
+                    species: species.name.clone(),
+                    pk_move: pk_move.clone(),
+                    generation: move_generation.clone().into(),
+                }
+            });
+        }
+    }
+
+    // Egg moves that each have a chain of their own may still need different fathers
+    if let Some(generation) = generation
+        && *generation < Generation::GEN6
+        && egg_moves.len() > 1
+        && unchained.is_empty()
+    {
+        let moves: BTreeSet<String> = egg_moves.iter().cloned().collect();
+        fetches.skipped = 0;
+        if !has_father_chain(conn, &mother, &moves, generation, &mut HashSet::new(), &mut fetches)? {
+            errors.push(if fetches.skipped > 0 {
+                UncheckedFathersError {
+                    species: species.name.clone(),
+                    moves: moves.iter().join(", "),
+                    generation: generation.clone().into(),
+                    unchecked: fetches.skipped.to_string(),
+                }
+            } else {
+                IncompatibleEggMovesError {
+                    species: species.name.clone(),
+                    moves: moves.iter().join(", "),
+                    generation: generation.clone().into(),
+                }
+            });
+        }
+    }
+
+    Ok(errors)
+}
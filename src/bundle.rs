@@ -0,0 +1,126 @@
+use crate::cache::{cache_file_path, cache_wal_file_paths, fetch_fetched_at_range, fetch_schema_version, SCHEMA_VERSION};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use miette::{miette, IntoDiagnostic, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{create_dir_all, read, remove_file, rename, write, File};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Identifies a file as a PokeSpecRS cache bundle
+const BUNDLE_FORMAT: &str = "pokespecrs-cache";
+
+/// Describes the cache inside a bundle. A bundle is gzipped, and holds this header as a line of JSON
+/// followed by the bytes of the cache's SQLite database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleHeader {
+    pub format: String,
+    pub schema_version: i32,
+    pub exported_at: DateTime<Utc>,
+    pub oldest_data: Option<DateTime<Utc>>, // When the oldest cached PokeAPI data was fetched. None if nothing is cached.
+    pub newest_data: Option<DateTime<Utc>>, // When the newest cached PokeAPI data was fetched
+    pub checksum: String,                   // SHA-256 of the database, in hex
+}
+
+impl BundleHeader {
+    /// Summarize the bundle's schema version and how old its PokeAPI data is, e.g. for a message after exporting
+    pub fn describe(&self) -> String {
+        let data = match (self.oldest_data, self.newest_data) {
+            (Some(oldest), Some(newest)) => format!(
+                "PokeAPI data fetched between {} and {}",
+                oldest.format("%Y-%m-%d %H:%M UTC"),
+                newest.format("%Y-%m-%d %H:%M UTC")
+            ),
+            _ => String::from("no PokeAPI data"),
+        };
+        format!("schema version {}, {}", self.schema_version, data)
+    }
+}
+
+/// Hex-encoded SHA-256 of some bytes
+fn checksum(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Write the cache to a bundle that can be imported on another machine. Returns the bundle's header.
+pub fn export_cache(conn: &Connection, file: &Path) -> Result<BundleHeader> {
+    // Copy the database first, so the bundle is consistent even if the cache is being written to
+    let snapshot = file.with_extension("db3.tmp");
+    if snapshot.exists() {
+        remove_file(&snapshot).into_diagnostic()?;
+    }
+    conn.execute("VACUUM INTO ?1;", rusqlite::params![snapshot.to_string_lossy()])
+        .into_diagnostic()?;
+    let database = read(&snapshot).into_diagnostic()?;
+    remove_file(&snapshot).into_diagnostic()?;
+
+    let fetched_at = fetch_fetched_at_range(conn)?;
+    let header = BundleHeader {
+        format: String::from(BUNDLE_FORMAT),
+        schema_version: fetch_schema_version(conn)?,
+        exported_at: Utc::now(),
+        oldest_data: fetched_at.and_then(|(oldest, _)| DateTime::from_timestamp(oldest, 0)),
+        newest_data: fetched_at.and_then(|(_, newest)| DateTime::from_timestamp(newest, 0)),
+        checksum: checksum(&database),
+    };
+
+    let mut encoder = GzEncoder::new(File::create(file).into_diagnostic()?, Compression::best());
+    writeln!(encoder, "{}", serde_json::to_string(&header).into_diagnostic()?).into_diagnostic()?;
+    encoder.write_all(&database).into_diagnostic()?;
+    encoder.finish().into_diagnostic()?;
+    Ok(header)
+}
+
+/// Replace the cache with the one in a bundle. Returns the bundle's header.
+///
+/// Bundles from a different schema version, or whose database doesn't match its checksum, are refused
+/// and the existing cache is left alone.
+pub fn import_cache(file: &Path) -> Result<BundleHeader> {
+    let mut contents: Vec<u8> = Vec::new();
+    GzDecoder::new(File::open(file).into_diagnostic()?)
+        .read_to_end(&mut contents)
+        .map_err(|_| miette!("{} is not a cache bundle", file.display()))?;
+
+    let split = contents
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .ok_or(miette!("{} is not a cache bundle", file.display()))?;
+    let (header, database) = (&contents[..split], &contents[split + 1..]);
+    let header: BundleHeader =
+        serde_json::from_slice(header).map_err(|_| miette!("{} is not a cache bundle", file.display()))?;
+
+    if header.format != BUNDLE_FORMAT {
+        return Err(miette!("{} is not a cache bundle", file.display()));
+    }
+    if header.schema_version != SCHEMA_VERSION {
+        return Err(miette!(
+            help = "Export the cache again with the same version of PokeSpecRS that will import it",
+            "The bundle has schema version {}, but this version of PokeSpecRS uses schema version {}",
+            header.schema_version,
+            SCHEMA_VERSION
+        ));
+    }
+    if checksum(database) != header.checksum {
+        return Err(miette!("The bundle's checksum doesn't match. It may be corrupted or incomplete."));
+    }
+
+    // Only swap the new cache in once it's fully written
+    let path = cache_file_path();
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).into_diagnostic()?;
+    }
+    let staged = path.with_extension("db3.import");
+    write(&staged, database).into_diagnostic()?;
+    // The old cache's write-ahead log would otherwise be applied to the new one
+    for wal_file in cache_wal_file_paths() {
+        if wal_file.exists() {
+            remove_file(wal_file).into_diagnostic()?;
+        }
+    }
+    rename(&staged, &path).into_diagnostic()?;
+    Ok(header)
+}
@@ -1,33 +1,88 @@
 use crate::api::pokemon_move::{MoveLearnMethod, PokeMove};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, remove_file};
 
-use crate::api::{api_get_balls, api_get_pokemon, api_get_pokemon_abilities, api_get_pokemon_moves};
-use crate::enums::{Generation, LearnMethod};
-use miette::{Error, ErrorHook, IntoDiagnostic, Result};
+use crate::api::item::PokeItem;
+use crate::api::move_data::{PokeMoveData, PokeMovePastValues};
+use crate::api::ability::PokeAbility;
+use crate::api::egg_group::PokeEggGroup;
+use crate::api::evolution::PokeEvolution;
+use crate::api::form::PokeForm;
+use crate::api::species::PokeSpecies;
+use crate::api::pokemon::PokemonData;
+use crate::api::response::ApiResponse;
+use crate::api::{
+    api_get_balls, api_get_item, api_get_move, api_get_move_learners, api_get_pokemon_data, FetchMode, Fetcher,
+};
+use crate::enums::{DamageClass, Generation, LearnMethod, PokeType};
+use miette::{miette, Error, IntoDiagnostic, Result};
 use num_traits::{FromPrimitive, ToPrimitive};
-use rusqlite::Connection;
-use std::path::Path;
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, ToSql, Transaction, TransactionBehavior};
+use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::time::Duration;
 use crate::console::info;
 
 const CACHE_PATH: &str = ".pokespecrs/";
 const CACHE_FNAME: &str = "cache.db3";
 
+/// The version of the cache's layout, stored in the cache as SQLite's `user_version`.
+/// Bump this whenever a table or column is added, so that exported caches aren't imported by incompatible versions.
+pub const SCHEMA_VERSION: i32 = 4;
+
+/// Recorded alongside each fetched resource. Resources fetched by another version of PokeSpecRS are refreshed,
+/// as they may have been parsed differently.
+pub const SOURCE_VERSION: &str = concat!("pokeapi-v2/pokespecrs-", env!("CARGO_PKG_VERSION"));
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// How long to wait for another process to finish writing to the cache before giving up with `SQLITE_BUSY`
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
 const POKEMON_TABLE: &str = "pokemon";
 const PT_SPECIES_COL: &str = "species";
 const MOVE_TABLE: &str = "moves";
 
 /// Create a re-useable connection to the cache DB.
+///
+/// Many processes may share the cache, so it uses write-ahead logging to let them read while another writes,
+/// and each connection waits up to `BUSY_TIMEOUT` for the write lock instead of failing straight away.
 pub fn get_db_connection() -> Connection {
     create_dir_all(Path::new(CACHE_PATH)).expect("Failed to create dir for cache!");
-    Connection::open(Path::new(CACHE_PATH).join(CACHE_FNAME)).expect("Failed to connect to cache!")
+    let conn = Connection::open(Path::new(CACHE_PATH).join(CACHE_FNAME)).expect("Failed to connect to cache!");
+    conn.busy_timeout(BUSY_TIMEOUT).expect("Failed to configure cache!");
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<usize, String>(0))
+        .expect("Failed to configure cache!");
+    conn
+}
+
+/// Begin a transaction that takes the cache's write lock straight away, waiting for other processes to finish writing.
+/// A transaction that reads before it writes can't wait for the lock once another process has written, and fails instead.
+fn write_transaction(conn: &Connection) -> Result<Transaction<'_>> {
+    Transaction::new_unchecked(conn, TransactionBehavior::Immediate).into_diagnostic()
+}
+
+/// Where the cache lives on disk
+pub fn cache_file_path() -> PathBuf {
+    Path::new(CACHE_PATH).join(CACHE_FNAME)
+}
+
+/// The write-ahead log and shared-memory index SQLite keeps next to the cache while it's in use
+pub fn cache_wal_file_paths() -> [PathBuf; 2] {
+    let path = cache_file_path();
+    [path.with_extension("db3-wal"), path.with_extension("db3-shm")]
 }
 
 pub fn del_cache_on_disk() {
-    let path = Path::new(CACHE_PATH).join(CACHE_FNAME);
+    let path = cache_file_path();
     if path.exists() {
         remove_file(path).expect("Failed to delete cache!");
+        for path in cache_wal_file_paths() {
+            if path.exists() {
+                remove_file(path).expect("Failed to delete cache!");
+            }
+        }
         println!("Cache deleted!");
     }
 }
@@ -35,17 +90,165 @@ pub fn del_cache_on_disk() {
 ///
 /// The cache has a few tables, each of which is centered around the `pokemon` table.
 /// Each related table links key data elements to a specific pokemon via the `species` foreign key.
+/// A row in `pokemon` is a specific form (e.g. `raichu-alola`), which the `forms` table links to its species.
+///
+/// Other processes may be setting up the same cache, so it's all done in one transaction.
+/// Caches already at `SCHEMA_VERSION` are left alone, without taking the write lock.
 pub fn set_up_db(connection: &Connection) -> Result<()> {
+    if fetch_schema_version(connection)? == SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tx = write_transaction(connection)?;
     connection
         .execute(
             "CREATE TABLE IF NOT EXISTS pokemon (
                 id INTEGER PRIMARY KEY,
+                species VARCHAR NOT NULL COLLATE NOCASE,
+                fetched_at INTEGER,
+                source_version VARCHAR
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+    add_column_if_missing(connection, "pokemon", "fetched_at", "INTEGER")?;
+    add_column_if_missing(connection, "pokemon", "source_version", "VARCHAR")?;
+
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS species (
+                id INTEGER PRIMARY KEY,
+                name VARCHAR NOT NULL COLLATE NOCASE,
+                is_legendary INTEGER,
+                is_mythical INTEGER,
+                is_baby INTEGER,
+                gender_rate INTEGER,
+                evolution_chain INTEGER,
+                generation INTEGER
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+    add_column_if_missing(connection, "species", "is_legendary", "INTEGER")?;
+    add_column_if_missing(connection, "species", "is_mythical", "INTEGER")?;
+    add_column_if_missing(connection, "species", "is_baby", "INTEGER")?;
+    add_column_if_missing(connection, "species", "gender_rate", "INTEGER")?;
+    add_column_if_missing(connection, "species", "evolution_chain", "INTEGER")?;
+    add_column_if_missing(connection, "species", "generation", "INTEGER")?;
+
+    // Every pokemon (form) of a species, whether or not it has been cached itself
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS varieties (
+                id INTEGER PRIMARY KEY,
+                species VARCHAR NOT NULL COLLATE NOCASE,
+                pokemon VARCHAR NOT NULL COLLATE NOCASE,
+                slot INTEGER NOT NULL
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+
+    // Each row is a single link in an evolution chain. Species are referred to by name, as most of a chain won't be cached.
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS evolutions (
+                id INTEGER PRIMARY KEY,
+                chain INTEGER NOT NULL,
+                species VARCHAR NOT NULL COLLATE NOCASE,
+                evolves_from VARCHAR COLLATE NOCASE,
+                min_level INTEGER
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+    add_column_if_missing(connection, "evolutions", "min_level", "INTEGER")?;
+
+    // Egg groups are cached whole, so each row is the membership of a species that may not be cached itself
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS egg_groups (
+                id INTEGER PRIMARY KEY,
+                name VARCHAR NOT NULL COLLATE NOCASE,
                 species VARCHAR NOT NULL COLLATE NOCASE
             );",
             (),
         )
         .into_diagnostic()?;
 
+    // Details of each move itself, as opposed to the `moves` table, which holds how each pokemon learns them
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS move_data (
+                id INTEGER PRIMARY KEY,
+                name VARCHAR NOT NULL COLLATE NOCASE,
+                generation INTEGER,
+                type INTEGER,
+                damage_class INTEGER,
+                power INTEGER,
+                accuracy INTEGER,
+                pp INTEGER,
+                priority INTEGER,
+                fetched_at INTEGER,
+                source_version VARCHAR
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+    add_column_if_missing(connection, "move_data", "type", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "damage_class", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "power", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "accuracy", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "pp", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "priority", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "fetched_at", "INTEGER")?;
+    add_column_if_missing(connection, "move_data", "source_version", "VARCHAR")?;
+
+    // The values a move had before it was changed, e.g. Flamethrower had 95 power before Gen 6
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS move_past_values (
+                id INTEGER PRIMARY KEY,
+                move_id INTEGER NOT NULL,
+                generation INTEGER NOT NULL,
+                type INTEGER,
+                power INTEGER,
+                accuracy INTEGER,
+                pp INTEGER,
+                FOREIGN KEY(move_id) REFERENCES move_data(id)
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+
+    // Every pokemon that can learn a move by any method in any generation, used to find breeding partners
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS move_learners (
+                id INTEGER PRIMARY KEY,
+                move VARCHAR NOT NULL COLLATE NOCASE,
+                pokemon VARCHAR NOT NULL COLLATE NOCASE
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+
+    // Links each pokemon (i.e. a specific form, such as `raichu-alola`) to the species it is a form of
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS forms (
+                id INTEGER PRIMARY KEY,
+                species_id INTEGER NOT NULL,
+                pokemon_id INTEGER NOT NULL,
+                is_default INTEGER NOT NULL,
+                generation INTEGER,
+                FOREIGN KEY(species_id) REFERENCES species(id),
+                FOREIGN KEY(pokemon_id) REFERENCES pokemon(id)
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+
     connection
         .execute(
             "CREATE TABLE IF NOT EXISTS moves (
@@ -60,6 +263,10 @@ pub fn set_up_db(connection: &Connection) -> Result<()> {
             (),
         )
         .into_diagnostic()?;
+    // Legality checks and `search` look moves up by name for a given pokemon
+    connection
+        .execute("CREATE INDEX IF NOT EXISTS moves_name_species ON moves (name, species_id);", ())
+        .into_diagnostic()?;
 
     connection
         .execute(
@@ -67,12 +274,70 @@ pub fn set_up_db(connection: &Connection) -> Result<()> {
             id INTEGER PRIMARY KEY,\
             name VARCHAR NOT NULL COLLATE NOCASE,\
              species_id INTEGER NOT NULL,
+            slot INTEGER,
+            is_hidden INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY(species_id) REFERENCES pokemon(id)
+        );",
+            (),
+        )
+        .into_diagnostic()?;
+    // Caches created before ability slots were tracked need the new columns
+    add_column_if_missing(connection, "abilities", "slot", "INTEGER")?;
+    add_column_if_missing(connection, "abilities", "is_hidden", "INTEGER NOT NULL DEFAULT 0")?;
+
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS types (
+            id INTEGER PRIMARY KEY,
+            slot INTEGER NOT NULL,
+            type INTEGER NOT NULL,
+            species_id INTEGER NOT NULL,
             FOREIGN KEY(species_id) REFERENCES pokemon(id)
         );",
             (),
         )
         .into_diagnostic()?;
 
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS base_stats (
+            id INTEGER PRIMARY KEY,
+            stat VARCHAR NOT NULL,
+            value INTEGER NOT NULL,
+            species_id INTEGER NOT NULL,
+            FOREIGN KEY(species_id) REFERENCES pokemon(id)
+        );",
+            (),
+        )
+        .into_diagnostic()?;
+
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS items (
+            id INTEGER PRIMARY KEY,
+            name VARCHAR NOT NULL COLLATE NOCASE,
+            category VARCHAR NOT NULL,
+            fetched_at INTEGER,
+            source_version VARCHAR
+        );",
+            (),
+        )
+        .into_diagnostic()?;
+    add_column_if_missing(connection, "items", "fetched_at", "INTEGER")?;
+    add_column_if_missing(connection, "items", "source_version", "VARCHAR")?;
+
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS item_generations (
+            id INTEGER PRIMARY KEY,
+            item_id INTEGER NOT NULL,
+            generation INTEGER NOT NULL,
+            FOREIGN KEY(item_id) REFERENCES items(id)
+        );",
+            (),
+        )
+        .into_diagnostic()?;
+
     connection
         .execute(
             "CREATE TABLE IF NOT EXISTS balls (id INTEGER PRIMARY KEY,\
@@ -81,6 +346,119 @@ pub fn set_up_db(connection: &Connection) -> Result<()> {
         )
         .into_diagnostic()?;
 
+    // Raw responses from PokeAPI, so that the normalized tables above can be derived again without the network
+    connection
+        .execute(
+            "CREATE TABLE IF NOT EXISTS responses (
+                route VARCHAR PRIMARY KEY,
+                body TEXT NOT NULL,
+                etag VARCHAR,
+                last_modified VARCHAR,
+                fetched_at INTEGER NOT NULL
+            );",
+            (),
+        )
+        .into_diagnostic()?;
+
+    // Shared rows may be cached by several processes at once, so each is unique and inserted with `ON CONFLICT`.
+    // Rows of a single pokemon, e.g. its moves, are only inserted by the process that inserted the pokemon.
+    let mut duplicates = 0;
+    duplicates += add_unique_index(connection, "pokemon_species", "pokemon", "species", &[])?;
+    duplicates += add_unique_index(connection, "species_name", "species", "name", &[("forms", "species_id")])?;
+    duplicates += add_unique_index(connection, "varieties_species_pokemon", "varieties", "species, pokemon", &[])?;
+    duplicates += add_unique_index(connection, "evolutions_chain_species", "evolutions", "chain, species", &[])?;
+    duplicates += add_unique_index(connection, "egg_groups_name_species", "egg_groups", "name, species", &[])?;
+    duplicates += add_unique_index(connection, "move_data_name", "move_data", "name", &[])?;
+    duplicates += add_unique_index(connection, "move_past_values_move_generation", "move_past_values", "move_id, generation", &[])?;
+    duplicates += add_unique_index(connection, "move_learners_move_pokemon", "move_learners", "move, pokemon", &[])?;
+    duplicates += add_unique_index(connection, "forms_pokemon", "forms", "pokemon_id", &[])?;
+    duplicates += add_unique_index(connection, "items_name", "items", "name", &[])?;
+    duplicates += add_unique_index(connection, "item_generations_item_generation", "item_generations", "item_id, generation", &[])?;
+    duplicates += add_unique_index(connection, "balls_name", "balls", "name", &[])?;
+    // Caches from before the indexes may have had duplicates, whose related rows now belong to nothing
+    if duplicates > 0 {
+        for (table, column, parent) in [
+            ("moves", "species_id", "pokemon"),
+            ("abilities", "species_id", "pokemon"),
+            ("types", "species_id", "pokemon"),
+            ("base_stats", "species_id", "pokemon"),
+            ("forms", "pokemon_id", "pokemon"),
+            ("forms", "species_id", "species"),
+            ("move_past_values", "move_id", "move_data"),
+            ("item_generations", "item_id", "items"),
+        ] {
+            connection
+                .execute(format!("DELETE FROM {table} WHERE {column} NOT IN (SELECT id FROM {parent});").as_str(), ())
+                .into_diagnostic()?;
+        }
+    }
+
+    // Every table and column is now in place, so the cache matches the current layout
+    connection
+        .pragma_update(None, "user_version", SCHEMA_VERSION)
+        .into_diagnostic()?;
+
+    tx.commit().into_diagnostic()
+}
+
+/// Add a unique index to an existing table, unless it already has it.
+/// Rows that would break the index are deleted first, keeping the earliest. Rows in `references`, given as table and
+/// column, that point at a deleted row are pointed at the kept one instead. Returns how many were deleted.
+fn add_unique_index(
+    connection: &Connection,
+    index: &str,
+    table: &str,
+    columns: &str,
+    references: &[(&str, &str)],
+) -> Result<usize> {
+    let mut stmt = connection
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'index' AND name = ?1;")
+        .into_diagnostic()?;
+    if stmt.exists(rusqlite::params![index]).into_diagnostic()? {
+        return Ok(0);
+    }
+
+    let same_key = columns
+        .split(", ")
+        .map(|column| format!("kept.{column} IS duplicate.{column}"))
+        .collect::<Vec<String>>()
+        .join(" AND ");
+    for (ref_table, ref_column) in references {
+        connection
+            .execute(
+                format!(
+                    "UPDATE {ref_table} SET {ref_column} = (SELECT MIN(kept.id) FROM {table} kept JOIN {table} duplicate \
+                    ON {same_key} WHERE duplicate.id = {ref_table}.{ref_column}) \
+                    WHERE {ref_column} NOT IN (SELECT MIN(id) FROM {table} GROUP BY {columns});"
+                )
+                .as_str(),
+                (),
+            )
+            .into_diagnostic()?;
+    }
+
+    let duplicates = connection
+        .execute(
+            format!("DELETE FROM {table} WHERE id NOT IN (SELECT MIN(id) FROM {table} GROUP BY {columns});").as_str(),
+            (),
+        )
+        .into_diagnostic()?;
+    connection
+        .execute(format!("CREATE UNIQUE INDEX {index} ON {table} ({columns});").as_str(), ())
+        .into_diagnostic()?;
+    Ok(duplicates)
+}
+
+/// Add a column to an existing table, unless the table already has it
+fn add_column_if_missing(connection: &Connection, table: &str, column: &str, declaration: &str) -> Result<()> {
+    let mut stmt = connection
+        .prepare(format!("SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?1;").as_str())
+        .into_diagnostic()?;
+    if !stmt.exists(rusqlite::params![column]).into_diagnostic()? {
+        connection
+            .execute(format!("ALTER TABLE {table} ADD COLUMN {column} {declaration};").as_str(), ())
+            .into_diagnostic()?;
+    }
     Ok(())
 }
 
@@ -89,6 +467,61 @@ pub fn is_cache() -> bool {
     Path::new(CACHE_PATH).join(CACHE_FNAME).exists()
 }
 
+/// Retrieve the version of the layout a cache was set up with. Caches set up before versions were tracked have `0`.
+pub fn fetch_schema_version(conn: &Connection) -> Result<i32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0)).into_diagnostic()
+}
+
+/// Retrieve when the oldest and newest cached PokeAPI data was fetched, as Unix times. Returns `None` if nothing with a
+/// fetch time is cached.
+pub fn fetch_fetched_at_range(conn: &Connection) -> Result<Option<(i64, i64)>> {
+    conn.query_row(
+        "SELECT MIN(fetched_at), MAX(fetched_at) FROM (SELECT fetched_at FROM pokemon UNION ALL SELECT fetched_at FROM move_data \
+        UNION ALL SELECT fetched_at FROM items UNION ALL SELECT fetched_at FROM responses);",
+        [],
+        |row| Ok(row.get::<usize, Option<i64>>(0)?.zip(row.get::<usize, Option<i64>>(1)?)),
+    )
+    .into_diagnostic()
+}
+
+/// Insert or replace the stored response for each route, without a transaction of their own, so that they're stored
+/// along with whatever was derived from them
+fn cache_responses(conn: &Connection, responses: &[ApiResponse]) -> Result<()> {
+    for response in responses {
+        conn.execute(
+            "INSERT INTO responses (route, body, etag, last_modified, fetched_at) VALUES (?1, ?2, ?3, ?4, ?5) \
+            ON CONFLICT (route) DO UPDATE SET body = ?2, etag = ?3, last_modified = ?4, fetched_at = ?5;",
+            rusqlite::params![response.route, response.body, response.etag, response.last_modified, response.fetched_at],
+        )
+        .into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Retrieve the stored response for a route, if there is one
+pub fn fetch_response(conn: &Connection, route: &str) -> Result<Option<ApiResponse>> {
+    let mut stmt = conn
+        .prepare("SELECT route, body, etag, last_modified, fetched_at FROM responses WHERE route = ?1;")
+        .into_diagnostic()?;
+    stmt.query_row(rusqlite::params![route], |row| {
+        Ok(ApiResponse {
+            route: row.get(0)?,
+            body: row.get(1)?,
+            etag: row.get(2)?,
+            last_modified: row.get(3)?,
+            fetched_at: row.get(4)?,
+        })
+    })
+    .optional()
+    .into_diagnostic()
+}
+
+/// Create a `Fetcher` that checks the cache's stored responses before asking PokeAPI, or instead of it when offline.
+/// The responses it collects are stored by whichever function caches what was derived from them.
+pub fn response_fetcher(conn: &Connection, mode: FetchMode) -> Fetcher<'_> {
+    Fetcher::new(mode, move |route| fetch_response(conn, route).ok().flatten())
+}
+
 /// Check if a given species of pokemon has already been cached
 pub fn is_species_cached(connection: &Connection, species: &str) -> bool {
     let stmt =
@@ -104,6 +537,33 @@ pub fn is_species_cached(connection: &Connection, species: &str) -> bool {
     }
 }
 
+/// Retrieve the name and id of every cached pokemon, in the order they were cached
+pub fn fetch_cached_pokemon(conn: &Connection) -> Result<Vec<(String, i32)>> {
+    let mut stmt = conn.prepare("SELECT species, id FROM pokemon ORDER BY id;").into_diagnostic()?;
+    stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .into_diagnostic()?
+        .collect::<Result<Vec<(String, i32)>, _>>()
+        .into_diagnostic()
+}
+
+/// Retrieve the name of every move whose details have been cached, in the order they were cached
+pub fn fetch_cached_move_data(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM move_data ORDER BY id;").into_diagnostic()?;
+    stmt.query_map([], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<String>, _>>()
+        .into_diagnostic()
+}
+
+/// Retrieve the name of every cached item, in the order they were cached
+pub fn fetch_cached_items(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM items ORDER BY id;").into_diagnostic()?;
+    stmt.query_map([], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<String>, _>>()
+        .into_diagnostic()
+}
+
 pub fn fetch_species_id(connection: &Connection, species: &str) -> Result<i32> {
     let stmt =
         connection.prepare(format!("SELECT * FROM pokemon WHERE species = '{species}'").as_str());
@@ -119,36 +579,40 @@ pub fn fetch_species_id(connection: &Connection, species: &str) -> Result<i32> {
     }
 }
 
-/// Insert the given species of Pokemon into the `pokemon` table in the cache
-pub fn cache_species(connection: &Connection, species: &str) -> Result<()> {
+/// Insert the given species of Pokemon into the `pokemon` table in the cache.
+/// Returns `false` if it was already there, e.g. because another process cached it meanwhile.
+///
+/// This and the other parts of caching a pokemon don't begin a transaction of their own; see `cache_pokemon_data`.
+fn cache_species(connection: &Connection, species: &str) -> Result<bool> {
     let stmt = connection.execute(
-        format!("INSERT INTO pokemon (species) VALUES ('{}');", species).as_str(),
-        (),
+        "INSERT INTO pokemon (species, fetched_at, source_version) VALUES (?1, ?2, ?3) ON CONFLICT (species) DO NOTHING;",
+        rusqlite::params![species, Utc::now().timestamp(), SOURCE_VERSION],
     );
 
     match stmt {
-        Ok(_) => Ok(()),
+        Ok(inserted) => Ok(inserted > 0),
         Err(err) => Err(err).into_diagnostic(),
     }
 }
 
 /// For a given species and vector of moves, insert the moves into the cache
-pub fn cache_moves(connection: &Connection, moves: &Vec<PokeMove>, species_id: i32) -> Result<()> {
-    let mut buffer: Vec<String> = vec![String::from("BEGIN;")];
-
+fn cache_moves(connection: &Connection, moves: &Vec<PokeMove>, species_id: i32) -> Result<()> {
+    let mut stmt = connection
+        .prepare("INSERT INTO moves (name, species_id, method, level_learned_at, generation) VALUES (?1, ?2, ?3, ?4, ?5);")
+        .into_diagnostic()?;
     for pk_move in moves {
         for method in &pk_move.generations {
-            buffer.push(format!("INSERT INTO moves (name, species_id, method, level_learned_at, generation) VALUES ('{}', '{}', '{}', '{}', '{}');", pk_move.name, species_id, method.method.to_i32().unwrap(), method.level_learned_at.unwrap(), method.generation.to_i32().unwrap()))
+            stmt.execute(rusqlite::params![
+                pk_move.name,
+                species_id,
+                method.method.to_i32(),
+                method.level_learned_at,
+                method.generation.to_i32()
+            ])
+            .into_diagnostic()?;
         }
     }
-
-    buffer.push(String::from("COMMIT;"));
-
-    let res = connection.execute_batch(buffer.join(" ").as_str());
-    match res {
-        Ok(_) => Ok(()),
-        Err(err) => Err(err).into_diagnostic(), // Pass error up
-    }
+    Ok(())
 }
 
 /// For a given species and move, retrieve all the learning methods for that move from the cache
@@ -186,31 +650,81 @@ pub fn fetch_move_methods(
     }
 }
 
-/// For a given species and vector of abilities, insert each ability into the cache
-pub fn cache_abilities(conn: &Connection, abilities: &Vec<String>, species_id: i32) -> Result<()> {
-    let mut buffer: Vec<String> = vec![String::from("BEGIN;")];
+/// Retrieve every move a given species can learn from the cache, along with all the methods it learns each by
+pub fn fetch_learnset(conn: &Connection, species_id: i32) -> Result<Vec<PokeMove>> {
+    let mut stmt = conn
+        .prepare("SELECT name, method, level_learned_at, generation FROM moves WHERE species_id = ?1 ORDER BY name;")
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![species_id]).into_diagnostic()?;
 
-    for ability in abilities {
-        buffer.push(format!(
-            "INSERT INTO abilities (name, species_id) VALUES ('{ability}', '{species_id}');",
-        ))
+    let mut learnset: Vec<PokeMove> = Vec::new();
+    while let Some(row) = rows.next().into_diagnostic()? {
+        let name: String = row.get(0).into_diagnostic()?;
+        let method = MoveLearnMethod {
+            method: LearnMethod::from_i32(row.get(1).into_diagnostic()?).unwrap(),
+            level_learned_at: row.get(2).into_diagnostic()?,
+            generation: Generation::from_i32(row.get(3).into_diagnostic()?).unwrap(),
+        };
+
+        // Rows are ordered by name, so every method of a move is contiguous
+        match learnset.last_mut() {
+            Some(pk_move) if pk_move.name == name => {
+                pk_move.generations.insert(method);
+            }
+            _ => learnset.push(PokeMove {
+                name,
+                generations: HashSet::from([method]),
+            }),
+        }
     }
+    Ok(learnset)
+}
 
-    buffer.push(String::from("COMMIT;"));
+/// For a given species and vector of types, insert each type into the cache
+fn cache_types(conn: &Connection, types: &[PokeType], species_id: i32) -> Result<()> {
+    for (slot, poke_type) in types.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO types (slot, type, species_id) VALUES (?1, ?2, ?3);",
+            rusqlite::params![slot + 1, poke_type.to_i32(), species_id],
+        )
+        .into_diagnostic()?;
+    }
+    Ok(())
+}
 
-    let res = conn.execute_batch(buffer.join(" ").as_str());
-    match res {
-        Ok(_) => Ok(()),
-        Err(err) => Err(err).into_diagnostic(), // Pass error up
+/// Retrieve the types of a given species, in slot order. Species cached before types were tracked have none.
+pub fn fetch_types(conn: &Connection, species_id: i32) -> Result<Vec<PokeType>> {
+    let mut stmt = conn
+        .prepare("SELECT type FROM types WHERE species_id = ?1 ORDER BY slot;")
+        .into_diagnostic()?;
+    let types = stmt
+        .query_map(rusqlite::params![species_id], |row| row.get::<usize, i32>(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<i32>, _>>()
+        .into_diagnostic()?;
+    Ok(types.into_iter().filter_map(PokeType::from_i32).collect())
+}
+
+/// For a given species and vector of abilities, insert each ability into the cache
+fn cache_abilities(conn: &Connection, abilities: &Vec<PokeAbility>, species_id: i32) -> Result<()> {
+    for ability in abilities {
+        conn.execute(
+            "INSERT INTO abilities (name, species_id, slot, is_hidden) VALUES (?1, ?2, ?3, ?4);",
+            rusqlite::params![ability.name, species_id, ability.slot, ability.is_hidden],
+        )
+        .into_diagnostic()?;
     }
+    Ok(())
 }
 
-pub fn fetch_abilities(conn: &Connection, species_id: i32) -> Result<HashSet<String>> {
-    let stmt = conn.prepare("SELECT * FROM abilities WHERE species_id = ?1;");
+/// Retrieve every ability of a given species from the cache, ordered by slot.
+/// Abilities cached before slots were tracked have a slot of 0 and are treated as regular abilities.
+pub fn fetch_abilities(conn: &Connection, species_id: i32) -> Result<Vec<PokeAbility>> {
+    let stmt = conn.prepare("SELECT name, slot, is_hidden FROM abilities WHERE species_id = ?1 ORDER BY slot;");
 
     match stmt {
         Ok(mut res) => {
-            let mut abilities: HashSet<String> = HashSet::new();
+            let mut abilities: Vec<PokeAbility> = Vec::new();
             let ability_sql = res.query(rusqlite::params![species_id]);
 
             match ability_sql {
@@ -218,7 +732,11 @@ pub fn fetch_abilities(conn: &Connection, species_id: i32) -> Result<HashSet<Str
                     // Must use weird next() interface as Rows object does not implement Iterator trait
                     while let Some(row) = ability_sql.next().into_diagnostic()? {
                         // idx corresponds to the order in which columns are declared in table creation statement
-                        abilities.insert(row.get(1).into_diagnostic()?);
+                        abilities.push(PokeAbility {
+                            name: row.get(0).into_diagnostic()?,
+                            slot: row.get::<usize, Option<u8>>(1).into_diagnostic()?.unwrap_or(0),
+                            is_hidden: row.get(2).into_diagnostic()?,
+                        });
                     }
                     Ok(abilities)
                 }
@@ -231,23 +749,118 @@ pub fn fetch_abilities(conn: &Connection, species_id: i32) -> Result<HashSet<Str
     }
 }
 
-/// For each type of pokeball known to PokeAPI, cache them.
-pub fn cache_balls(conn: &Connection, balls: HashSet<String>) -> Result<()> {
-    let mut buffer: Vec<String> = vec![String::from("BEGIN;")];
+/// For a given species and map of base stats, insert each base stat into the cache
+fn cache_base_stats(conn: &Connection, base_stats: &HashMap<String, u16>, species_id: i32) -> Result<()> {
+    for (stat, value) in base_stats {
+        conn.execute(
+            "INSERT INTO base_stats (stat, value, species_id) VALUES (?1, ?2, ?3);",
+            rusqlite::params![stat, value, species_id],
+        )
+        .into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Retrieve the base stats of a given species from the cache, keyed by stat name
+pub fn fetch_base_stats(conn: &Connection, species_id: i32) -> Result<HashMap<String, u16>> {
+    let mut stmt = conn
+        .prepare("SELECT stat, value FROM base_stats WHERE species_id = ?1;")
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![species_id]).into_diagnostic()?;
 
-    for ball in balls {
-        buffer.push(format!("INSERT INTO balls (name) VALUES ('{ball}');",))
+    let mut base_stats: HashMap<String, u16> = HashMap::new();
+    while let Some(row) = rows.next().into_diagnostic()? {
+        base_stats.insert(row.get(0).into_diagnostic()?, row.get(1).into_diagnostic()?);
     }
+    Ok(base_stats)
+}
 
-    buffer.push(String::from("COMMIT;"));
+/// Insert an item and each generation it appears in into the cache, along with the responses it was derived from
+pub fn cache_item(conn: &Connection, item: &PokeItem, responses: &[ApiResponse]) -> Result<()> {
+    let tx = write_transaction(conn)?;
+    cache_responses(&tx, responses)?;
+    let item_id: i64 = tx
+        .query_row(
+            "INSERT INTO items (name, category, fetched_at, source_version) VALUES (?1, ?2, ?3, ?4) \
+            ON CONFLICT (name) DO UPDATE SET category = ?2, fetched_at = ?3, source_version = ?4 RETURNING id;",
+            rusqlite::params![item.name, item.category, Utc::now().timestamp(), SOURCE_VERSION],
+            |row| row.get(0),
+        )
+        .into_diagnostic()?;
+
+    tx.execute("DELETE FROM item_generations WHERE item_id = ?1;", rusqlite::params![item_id])
+        .into_diagnostic()?;
+    for generation in &item.generations {
+        tx.execute(
+            "INSERT INTO item_generations (item_id, generation) VALUES (?1, ?2) ON CONFLICT DO NOTHING;",
+            rusqlite::params![item_id, generation.to_i32().unwrap()],
+        )
+        .into_diagnostic()?;
+    }
+
+    tx.commit().into_diagnostic()
+}
+
+/// Retrieve an item from the cache, if it has been cached
+pub fn fetch_item(conn: &Connection, item: &str) -> Result<Option<PokeItem>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, category FROM items WHERE name = ?1;")
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![item]).into_diagnostic()?;
+
+    let row = match rows.next().into_diagnostic()? {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+    let item_id: i64 = row.get(0).into_diagnostic()?;
+    let mut poke_item = PokeItem {
+        name: row.get(1).into_diagnostic()?,
+        category: row.get(2).into_diagnostic()?,
+        generations: HashSet::new(),
+    };
+
+    let mut stmt = conn
+        .prepare("SELECT generation FROM item_generations WHERE item_id = ?1;")
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![item_id]).into_diagnostic()?;
+    while let Some(row) = rows.next().into_diagnostic()? {
+        poke_item
+            .generations
+            .insert(Generation::from_i32(row.get(0).into_diagnostic()?).unwrap());
+    }
+
+    Ok(Some(poke_item))
+}
 
-    let res = conn.execute_batch(buffer.join(" ").as_str());
-    match res {
-        Ok(_) => Ok(()),
-        Err(err) => Err(err).into_diagnostic(), // Pass error up
+/// Retrieve an item from the cache, fetching and caching it from PokeAPI first if needed.
+/// Returns `None` if PokeAPI has no such item.
+pub fn get_and_cache_item(conn: &Connection, item: &str) -> Result<Option<PokeItem>> {
+    if let Some(poke_item) = fetch_item(conn, item)? {
+        return Ok(Some(poke_item));
+    }
+
+    info(format!("Fetching {item}'s info. This will only happen once!").as_str());
+    let fetcher = response_fetcher(conn, FetchMode::Online);
+    match api_get_item(&fetcher, item)? {
+        Some(poke_item) => {
+            cache_item(conn, &poke_item, &fetcher.take_responses())?;
+            Ok(Some(poke_item))
+        }
+        None => Ok(None),
     }
 }
 
+/// For each type of pokeball known to PokeAPI, cache them, along with the responses they were derived from.
+pub fn cache_balls(conn: &Connection, balls: HashSet<String>, responses: &[ApiResponse]) -> Result<()> {
+    let tx = write_transaction(conn)?;
+    cache_responses(&tx, responses)?;
+    for ball in balls {
+        tx.execute("INSERT INTO balls (name) VALUES (?1) ON CONFLICT DO NOTHING;", rusqlite::params![ball])
+            .into_diagnostic()?;
+    }
+    tx.commit().into_diagnostic()
+}
+
 /// Retrieve a set of each type of pokeball from the cache
 pub fn fetch_balls(conn: &Connection) -> Result<HashSet<String>> {
     let stmt = conn.prepare("SELECT * FROM balls");
@@ -271,34 +884,830 @@ pub fn fetch_balls(conn: &Connection) -> Result<HashSet<String>> {
     }
 }
 
+/// Link a cached pokemon to the species it is a form of, caching the species first if needed
+fn cache_form(conn: &Connection, form: &PokeForm, species_id: i32) -> Result<()> {
+    conn.execute(
+        "INSERT INTO species (name) VALUES (?1) ON CONFLICT DO NOTHING;",
+        rusqlite::params![form.species],
+    )
+    .into_diagnostic()?;
+    conn.execute(
+        "INSERT INTO forms (species_id, pokemon_id, is_default, generation) \
+        SELECT id, ?2, ?3, ?4 FROM species WHERE name = ?1 \
+        ON CONFLICT (pokemon_id) DO UPDATE SET species_id = excluded.species_id, is_default = ?3, generation = ?4;",
+        rusqlite::params![
+            form.species,
+            species_id,
+            form.is_default,
+            form.generation.as_ref().and_then(|generation| generation.to_i32())
+        ],
+    )
+    .into_diagnostic()?;
+    Ok(())
+}
+
+/// Insert or update the species-level details of a species
+fn cache_species_details(conn: &Connection, species: &PokeSpecies) -> Result<()> {
+    conn.execute(
+        "INSERT INTO species (name) VALUES (?1) ON CONFLICT DO NOTHING;",
+        rusqlite::params![species.name],
+    )
+    .into_diagnostic()?;
+    conn.execute(
+        "UPDATE species SET is_legendary = ?2, is_mythical = ?3, is_baby = ?4, gender_rate = ?5, evolution_chain = ?6, \
+        generation = ?7 WHERE name = ?1;",
+        rusqlite::params![
+            species.name,
+            species.is_legendary,
+            species.is_mythical,
+            species.is_baby,
+            species.gender_rate,
+            species.evolution_chain,
+            species.generation.as_ref().and_then(|generation| generation.to_i32())
+        ],
+    )
+    .into_diagnostic()?;
+    conn.execute("DELETE FROM varieties WHERE species = ?1;", rusqlite::params![species.name])
+        .into_diagnostic()?;
+    for (slot, pokemon) in species.varieties.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO varieties (species, pokemon, slot) VALUES (?1, ?2, ?3) ON CONFLICT DO UPDATE SET slot = ?3;",
+            rusqlite::params![species.name, pokemon, slot],
+        )
+        .into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Retrieve the species-level details of a species. Species cached before these were tracked have none.
+///
+/// Species cached before breeding data was tracked have no gender rate, egg groups, or evolution chain,
+/// and those cached before the `info` command have no generation or varieties.
+pub fn fetch_species_details(conn: &Connection, species: &str) -> Result<Option<PokeSpecies>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, is_legendary, is_mythical, is_baby, gender_rate, evolution_chain, generation FROM species \
+            WHERE name = ?1 AND is_legendary IS NOT NULL;",
+        )
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![species]).into_diagnostic()?;
+
+    match rows.next().into_diagnostic()? {
+        Some(row) => Ok(Some(PokeSpecies {
+            name: row.get(0).into_diagnostic()?,
+            is_legendary: row.get(1).into_diagnostic()?,
+            is_mythical: row.get(2).into_diagnostic()?,
+            is_baby: row.get(3).into_diagnostic()?,
+            gender_rate: row.get(4).into_diagnostic()?,
+            egg_groups: fetch_species_egg_groups(conn, species)?,
+            evolution_chain: row.get(5).into_diagnostic()?,
+            generation: row
+                .get::<usize, Option<i32>>(6)
+                .into_diagnostic()?
+                .and_then(Generation::from_i32),
+            varieties: fetch_species_varieties(conn, species)?,
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Retrieve the name of every pokemon (form) of a species, default first
+pub fn fetch_species_varieties(conn: &Connection, species: &str) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare("SELECT pokemon FROM varieties WHERE species = ?1 ORDER BY slot;")
+        .into_diagnostic()?;
+    stmt.query_map(rusqlite::params![species], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<String>, _>>()
+        .into_diagnostic()
+}
+
+/// Retrieve the species-level details of a species along with the id of its default form, if both have been cached
+pub fn fetch_cached_species_details(conn: &Connection, species: &str) -> Result<Option<(PokeSpecies, i32)>> {
+    let Some(details) = fetch_species_details(conn, species)? else {
+        return Ok(None);
+    };
+    match fetch_species_forms(conn, species)?.into_iter().find(|form| form.is_default) {
+        Some(form) => Ok(Some((details, fetch_species_id(conn, &form.pokemon)?))),
+        None => Ok(None),
+    }
+}
+
+/// Retrieve the species-level details of a species along with the id of its default form,
+/// fetching and caching it from PokeAPI first if needed.
+pub fn get_and_cache_species_details(conn: &Connection, species: &str) -> Result<Option<(PokeSpecies, i32)>> {
+    let (_, pokemon_id) = resolve_pokemon(conn, species, None)?;
+    Ok(fetch_species_details(conn, species)?.map(|details| (details, pokemon_id)))
+}
+
+/// Check if a given evolution chain has already been cached
+pub fn is_evolution_chain_cached(conn: &Connection, chain: i32) -> Result<bool> {
+    let mut stmt = conn.prepare("SELECT 1 FROM evolutions WHERE chain = ?1;").into_diagnostic()?;
+    stmt.exists(rusqlite::params![chain]).into_diagnostic()
+}
+
+/// Insert every link of an evolution chain into the cache
+fn cache_evolution_chain(conn: &Connection, evolutions: &Vec<PokeEvolution>) -> Result<()> {
+    for evolution in evolutions {
+        conn.execute(
+            "INSERT INTO evolutions (chain, species, evolves_from, min_level) VALUES (?1, ?2, ?3, ?4) \
+            ON CONFLICT DO UPDATE SET evolves_from = ?3, min_level = ?4;",
+            rusqlite::params![evolution.chain, evolution.species, evolution.evolves_from, evolution.min_level],
+        )
+        .into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Retrieve every link of a cached evolution chain
+pub fn fetch_evolution_chain(conn: &Connection, chain: i32) -> Result<Vec<PokeEvolution>> {
+    let mut stmt = conn
+        .prepare("SELECT chain, species, evolves_from, min_level FROM evolutions WHERE chain = ?1;")
+        .into_diagnostic()?;
+    stmt.query_map(rusqlite::params![chain], |row| {
+        Ok(PokeEvolution {
+            chain: row.get(0)?,
+            species: row.get(1)?,
+            evolves_from: row.get(2)?,
+            min_level: row.get(3)?,
+        })
+    })
+    .into_diagnostic()?
+    .collect::<Result<Vec<PokeEvolution>, _>>()
+    .into_diagnostic()
+}
+
+/// Check if a given egg group has already been cached
+pub fn is_egg_group_cached(conn: &Connection, egg_group: &str) -> Result<bool> {
+    let mut stmt = conn.prepare("SELECT 1 FROM egg_groups WHERE name = ?1;").into_diagnostic()?;
+    stmt.exists(rusqlite::params![egg_group]).into_diagnostic()
+}
+
+/// Insert the membership of an egg group into the cache
+fn cache_egg_group(conn: &Connection, egg_group: &PokeEggGroup) -> Result<()> {
+    for species in &egg_group.species {
+        conn.execute(
+            "INSERT INTO egg_groups (name, species) VALUES (?1, ?2) ON CONFLICT DO NOTHING;",
+            rusqlite::params![egg_group.name, species],
+        )
+        .into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Retrieve every species in a cached egg group
+pub fn fetch_egg_group(conn: &Connection, egg_group: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT species FROM egg_groups WHERE name = ?1;").into_diagnostic()?;
+    stmt.query_map(rusqlite::params![egg_group], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<String>, _>>()
+        .into_diagnostic()
+}
+
+/// Retrieve the egg groups of a species. Only egg groups that have been cached are known.
+pub fn fetch_species_egg_groups(conn: &Connection, species: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT name FROM egg_groups WHERE species = ?1;").into_diagnostic()?;
+    stmt.query_map(rusqlite::params![species], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<String>, _>>()
+        .into_diagnostic()
+}
+
+/// Insert the details of a move and its past values into the cache, replacing any that were cached before,
+/// along with the responses they were derived from
+pub fn cache_move_data(conn: &Connection, move_data: &PokeMoveData, responses: &[ApiResponse]) -> Result<()> {
+    let tx = write_transaction(conn)?;
+    cache_responses(&tx, responses)?;
+    let move_id: i64 = tx
+        .query_row(
+            "INSERT INTO move_data (name, generation, type, damage_class, power, accuracy, pp, priority, fetched_at, source_version) \
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+            ON CONFLICT (name) DO UPDATE SET generation = ?2, type = ?3, damage_class = ?4, power = ?5, accuracy = ?6, pp = ?7, \
+            priority = ?8, fetched_at = ?9, source_version = ?10 RETURNING id;",
+            rusqlite::params![
+                move_data.name,
+                move_data.generation.as_ref().and_then(|generation| generation.to_i32()),
+                move_data.move_type.and_then(|move_type| move_type.to_i32()),
+                move_data.damage_class.and_then(|damage_class| damage_class.to_i32()),
+                move_data.power,
+                move_data.accuracy,
+                move_data.pp,
+                move_data.priority,
+                Utc::now().timestamp(),
+                SOURCE_VERSION
+            ],
+            |row| row.get(0),
+        )
+        .into_diagnostic()?;
+
+    tx.execute("DELETE FROM move_past_values WHERE move_id = ?1;", rusqlite::params![move_id])
+        .into_diagnostic()?;
+    for past_values in &move_data.past_values {
+        tx.execute(
+            "INSERT INTO move_past_values (move_id, generation, type, power, accuracy, pp) VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+            ON CONFLICT DO NOTHING;",
+            rusqlite::params![
+                move_id,
+                past_values.generation.to_i32(),
+                past_values.move_type.and_then(|move_type| move_type.to_i32()),
+                past_values.power,
+                past_values.accuracy,
+                past_values.pp
+            ],
+        )
+        .into_diagnostic()?;
+    }
+
+    tx.commit().into_diagnostic()
+}
+
+/// Retrieve the details of a move from the cache, if it has been cached.
+/// Moves cached before their full details were tracked are treated as uncached, so that they get fetched again.
+pub fn fetch_move_data(conn: &Connection, pk_move: &str) -> Result<Option<PokeMoveData>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, generation, type, damage_class, power, accuracy, pp, priority FROM move_data \
+            WHERE name = ?1 AND priority IS NOT NULL;",
+        )
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![pk_move]).into_diagnostic()?;
+
+    let row = match rows.next().into_diagnostic()? {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+    let move_id: i64 = row.get(0).into_diagnostic()?;
+    let mut move_data = PokeMoveData {
+        name: row.get(1).into_diagnostic()?,
+        generation: row
+            .get::<usize, Option<i32>>(2)
+            .into_diagnostic()?
+            .and_then(Generation::from_i32),
+        move_type: row.get::<usize, Option<i32>>(3).into_diagnostic()?.and_then(PokeType::from_i32),
+        damage_class: row
+            .get::<usize, Option<i32>>(4)
+            .into_diagnostic()?
+            .and_then(DamageClass::from_i32),
+        power: row.get(5).into_diagnostic()?,
+        accuracy: row.get(6).into_diagnostic()?,
+        pp: row.get(7).into_diagnostic()?,
+        priority: row.get(8).into_diagnostic()?,
+        past_values: Vec::new(),
+    };
+
+    let mut stmt = conn
+        .prepare("SELECT generation, type, power, accuracy, pp FROM move_past_values WHERE move_id = ?1;")
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![move_id]).into_diagnostic()?;
+    while let Some(row) = rows.next().into_diagnostic()? {
+        move_data.past_values.push(PokeMovePastValues {
+            generation: Generation::from_i32(row.get(0).into_diagnostic()?).unwrap(),
+            move_type: row.get::<usize, Option<i32>>(1).into_diagnostic()?.and_then(PokeType::from_i32),
+            power: row.get(2).into_diagnostic()?,
+            accuracy: row.get(3).into_diagnostic()?,
+            pp: row.get(4).into_diagnostic()?,
+        });
+    }
+
+    Ok(Some(move_data))
+}
+
+/// Check if a move is known to the cache, either by its details or as a move some cached pokemon learns
+pub fn is_move_cached(conn: &Connection, pk_move: &str) -> Result<bool> {
+    let mut stmt = conn
+        .prepare("SELECT 1 FROM move_data WHERE name = ?1 UNION SELECT 1 FROM moves WHERE name = ?1;")
+        .into_diagnostic()?;
+    stmt.exists(rusqlite::params![pk_move]).into_diagnostic()
+}
+
+/// Retrieve the details of a move from the cache, fetching and caching it from PokeAPI first if needed.
+/// Returns `None` if PokeAPI has no such move.
+pub fn get_and_cache_move_data(conn: &Connection, pk_move: &str) -> Result<Option<PokeMoveData>> {
+    if let Some(move_data) = fetch_move_data(conn, pk_move)? {
+        return Ok(Some(move_data));
+    }
+
+    info(format!("Fetching {pk_move}'s info. This will only happen once!").as_str());
+    let fetcher = response_fetcher(conn, FetchMode::Online);
+    match api_get_move(&fetcher, pk_move)? {
+        Some(move_data) => {
+            cache_move_data(conn, &move_data, &fetcher.take_responses())?;
+            Ok(Some(move_data))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Retrieve every pokemon that can learn a move, if they have been cached
+pub fn fetch_move_learners(conn: &Connection, pk_move: &str) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare("SELECT pokemon FROM move_learners WHERE move = ?1;").into_diagnostic()?;
+    stmt.query_map(rusqlite::params![pk_move], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<HashSet<String>, _>>()
+        .into_diagnostic()
+}
+
+/// Retrieve every pokemon that can learn a move, fetching and caching them from PokeAPI first if needed
+pub fn get_and_cache_move_learners(conn: &Connection, pk_move: &str) -> Result<HashSet<String>> {
+    let learners = fetch_move_learners(conn, pk_move)?;
+    if !learners.is_empty() {
+        return Ok(learners);
+    }
+
+    info(format!("Fetching the learners of {pk_move}. This will only happen once!").as_str());
+    let fetcher = response_fetcher(conn, FetchMode::Online);
+    let learners = api_get_move_learners(&fetcher, pk_move)?;
+    let tx = write_transaction(conn)?;
+    cache_responses(&tx, &fetcher.take_responses())?;
+    for pokemon in &learners {
+        tx.execute(
+            "INSERT INTO move_learners (move, pokemon) VALUES (?1, ?2) ON CONFLICT DO NOTHING;",
+            rusqlite::params![pk_move, pokemon],
+        )
+        .into_diagnostic()?;
+    }
+    tx.commit().into_diagnostic()?;
+    Ok(learners)
+}
+
+/// Retrieve the species-form link for a cached pokemon. Pokemon cached before forms were tracked have none.
+pub fn fetch_form(conn: &Connection, species_id: i32) -> Result<Option<PokeForm>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT species.name, pokemon.species, forms.is_default, forms.generation FROM forms \
+            JOIN species ON species.id = forms.species_id \
+            JOIN pokemon ON pokemon.id = forms.pokemon_id \
+            WHERE forms.pokemon_id = ?1;",
+        )
+        .into_diagnostic()?;
+    let mut rows = stmt.query(rusqlite::params![species_id]).into_diagnostic()?;
+
+    match rows.next().into_diagnostic()? {
+        Some(row) => Ok(Some(PokeForm {
+            species: row.get(0).into_diagnostic()?,
+            pokemon: row.get(1).into_diagnostic()?,
+            is_default: row.get(2).into_diagnostic()?,
+            generation: row
+                .get::<usize, Option<i32>>(3)
+                .into_diagnostic()?
+                .and_then(Generation::from_i32),
+        })),
+        None => Ok(None),
+    }
+}
+
+/// Retrieve every cached form of a species
+pub fn fetch_species_forms(conn: &Connection, species: &str) -> Result<Vec<PokeForm>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT forms.pokemon_id FROM forms JOIN species ON species.id = forms.species_id \
+            WHERE species.name = ?1;",
+        )
+        .into_diagnostic()?;
+    let pokemon_ids: Vec<i32> = stmt
+        .query_map(rusqlite::params![species], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<i32>, _>>()
+        .into_diagnostic()?;
+
+    let mut forms: Vec<PokeForm> = Vec::new();
+    for pokemon_id in pokemon_ids {
+        forms.extend(fetch_form(conn, pokemon_id)?);
+    }
+    Ok(forms)
+}
+
 /// A convenience function to cache a species and all of its related fields all at once.
-pub fn cache_entire_pokemon(
+fn cache_entire_pokemon(
     conn: &Connection,
-    species: &str,
+    form: &PokeForm,
     poke_moves: &Vec<PokeMove>,
-    abilities: &Vec<String>,
+    abilities: &Vec<PokeAbility>,
+    types: &[PokeType],
+    base_stats: &HashMap<String, u16>,
 ) -> Result<i32, Error> {
-    cache_species(conn, species)?;
-    let species_id = fetch_species_id(conn, species)?;
+    if !cache_species(conn, &form.pokemon)? {
+        // Another process cached this pokemon while it was being fetched
+        return fetch_species_id(conn, &form.pokemon);
+    }
+    let species_id = fetch_species_id(conn, &form.pokemon)?;
+    cache_form(conn, form, species_id)?;
     cache_moves(conn, poke_moves, species_id)?;
     cache_abilities(conn, abilities, species_id)?;
+    cache_types(conn, types, species_id)?;
+    cache_base_stats(conn, base_stats, species_id)?;
     Ok(species_id)
 }
 
-/// A convenience function that pulls data from PokeAPI and then caches the results
-pub fn get_and_cache_pokemon(species: &str) -> Result<i32, Error> {
+/// Work out which cached pokemon a species and optional form refer to, fetching it from PokeAPI if needed.
+///
+/// Without a form, the species' default form is used. E.g. `urshifu` resolves to `urshifu-single-strike`.
+/// Returns the name and id of the pokemon in the cache.
+pub fn resolve_pokemon(conn: &Connection, species: &str, form: Option<&str>) -> Result<(String, i32), Error> {
+    let name = match form {
+        Some(form) => format!("{species}-{form}"),
+        None => species.to_string(),
+    };
+
+    if is_species_cached(conn, &name) {
+        return Ok((name.clone(), fetch_species_id(conn, &name)?));
+    }
+
+    if form.is_none()
+        && let Some(default_form) = fetch_species_forms(conn, species)?
+            .into_iter()
+            .find(|form| form.is_default)
+    {
+        return Ok((default_form.pokemon.clone(), fetch_species_id(conn, &default_form.pokemon)?));
+    }
+
+    get_and_cache_pokemon(&name).map_err(|e| match form {
+        Some(form) => miette!("{} has no form '{}'", species, form),
+        None => e,
+    })
+}
+
+/// Cache everything fetched about a pokemon, skipping the evolution chain and egg groups if they were cached meanwhile.
+/// Returns the name and id of the pokemon in the cache.
+///
+/// It's all cached in one transaction, so if any part fails nothing is cached and the pokemon is fetched again next time,
+/// rather than being cached with, e.g., no moves.
+pub fn cache_pokemon_data(conn: &Connection, pokemon_data: &PokemonData) -> Result<(String, i32), Error> {
+    let tx = write_transaction(conn)?;
+    let cached = ingest_pokemon_data(&tx, pokemon_data)?;
+    tx.commit().into_diagnostic()?;
+    Ok(cached)
+}
+
+/// Cache everything fetched about a pokemon, without a transaction of its own. See `cache_pokemon_data`.
+fn ingest_pokemon_data(conn: &Connection, pokemon_data: &PokemonData) -> Result<(String, i32), Error> {
+    cache_responses(conn, &pokemon_data.responses)?;
+    cache_species_details(conn, &pokemon_data.species)?;
+    if let Some(evolution) = pokemon_data.evolution_chain.first()
+        && !is_evolution_chain_cached(conn, evolution.chain)?
+    {
+        cache_evolution_chain(conn, &pokemon_data.evolution_chain)?;
+    }
+    for egg_group in &pokemon_data.egg_groups {
+        if !is_egg_group_cached(conn, &egg_group.name)? {
+            cache_egg_group(conn, egg_group)?;
+        }
+    }
+    let species_id = cache_entire_pokemon(
+        conn,
+        &pokemon_data.form,
+        &pokemon_data.moves,
+        &pokemon_data.abilities,
+        &pokemon_data.types,
+        &pokemon_data.base_stats,
+    )?;
+    Ok((pokemon_data.form.pokemon.clone(), species_id))
+}
+
+/// A convenience function that pulls data from PokeAPI and then caches the results.
+/// Returns the name and id of the pokemon in the cache.
+pub fn get_and_cache_pokemon(species: &str) -> Result<(String, i32), Error> {
     info(format!("Fetching {species}'s info. This will only happen once!").as_str());
     let conn = get_db_connection();
-    let pokemon_json = api_get_pokemon(species);
-    info("Fetching moves...");
-    let pokemon_moves = api_get_pokemon_moves(&pokemon_json);
-    info("Fetching abilities...");
-    let pokemon_abilities = api_get_pokemon_abilities(&pokemon_json);
+    let pokemon_data = api_get_pokemon_data(
+        &response_fetcher(&conn, FetchMode::Online),
+        species,
+        |chain| !is_evolution_chain_cached(&conn, chain).unwrap_or(false),
+        |egg_group| !is_egg_group_cached(&conn, egg_group).unwrap_or(false),
+    )?
+    .ok_or(miette!("PokeAPI has no pokemon or species named '{}'", species))?;
     info("Caching results...");
-    cache_entire_pokemon(&conn, &species, &pokemon_moves, &pokemon_abilities)
+    cache_pokemon_data(&conn, &pokemon_data)
+}
+
+/// Check whether a cached resource should be fetched again: it was fetched longer ago than `ttl_days`,
+/// by another version of PokeSpecRS, or before fetch times were recorded. Resources that aren't cached aren't stale.
+fn is_stale(conn: &Connection, table: &str, key_column: &str, key: &dyn ToSql, ttl_days: u64) -> Result<bool> {
+    let mut stmt = conn
+        .prepare(format!("SELECT fetched_at, source_version FROM {table} WHERE {key_column} = ?1;").as_str())
+        .into_diagnostic()?;
+    let fetched = stmt
+        .query_row(rusqlite::params![key], |row| {
+            Ok((row.get::<usize, Option<i64>>(0)?, row.get::<usize, Option<String>>(1)?))
+        })
+        .optional()
+        .into_diagnostic()?;
+
+    Ok(match fetched {
+        None => false,
+        Some((Some(fetched_at), Some(source_version))) if source_version == SOURCE_VERSION => {
+            Utc::now().timestamp() - fetched_at > ttl_days as i64 * SECONDS_PER_DAY
+        }
+        Some(_) => true,
+    })
+}
+
+/// Check whether a cached pokemon should be fetched again. See `is_stale`.
+pub fn is_pokemon_stale(conn: &Connection, pokemon_id: i32, ttl_days: u64) -> Result<bool> {
+    is_stale(conn, POKEMON_TABLE, "id", &pokemon_id, ttl_days)
+}
+
+/// Check whether a cached move should be fetched again. See `is_stale`.
+pub fn is_move_data_stale(conn: &Connection, pk_move: &str, ttl_days: u64) -> Result<bool> {
+    is_stale(conn, "move_data", "name", &pk_move, ttl_days)
+}
+
+/// Check whether a cached item should be fetched again. See `is_stale`.
+pub fn is_item_stale(conn: &Connection, item: &str, ttl_days: u64) -> Result<bool> {
+    is_stale(conn, "items", "name", &item, ttl_days)
+}
+
+/// Retrieve the name of every cached resource in a table that should be fetched again. See `is_stale`.
+fn fetch_stale(conn: &Connection, table: &str, name_column: &str, ttl_days: u64) -> Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare(
+            format!(
+                "SELECT {name_column} FROM {table} WHERE fetched_at IS NULL OR source_version IS NOT ?1 OR fetched_at < ?2 \
+                ORDER BY id;"
+            )
+            .as_str(),
+        )
+        .into_diagnostic()?;
+    let oldest = Utc::now().timestamp() - ttl_days as i64 * SECONDS_PER_DAY;
+    stmt.query_map(rusqlite::params![SOURCE_VERSION, oldest], |row| row.get(0))
+        .into_diagnostic()?
+        .collect::<Result<Vec<String>, _>>()
+        .into_diagnostic()
+}
+
+/// Retrieve the name of every cached pokemon, move, and item that should be fetched again, in that order
+pub fn fetch_stale_resources(conn: &Connection, ttl_days: u64) -> Result<(Vec<String>, Vec<String>, Vec<String>)> {
+    Ok((
+        fetch_stale(conn, POKEMON_TABLE, PT_SPECIES_COL, ttl_days)?,
+        fetch_stale(conn, "move_data", "name", ttl_days)?,
+        fetch_stale(conn, "items", "name", ttl_days)?,
+    ))
+}
+
+/// Delete everything cached about a pokemon, along with its evolution chain and egg groups so they get fetched again
+fn purge_pokemon_data(conn: &Connection, pokemon: &str, species: &PokeSpecies) -> Result<()> {
+    for table in ["moves", "abilities", "types", "base_stats"] {
+        conn.execute(
+            format!("DELETE FROM {table} WHERE species_id IN (SELECT id FROM pokemon WHERE species = ?1);").as_str(),
+            rusqlite::params![pokemon],
+        )
+        .into_diagnostic()?;
+    }
+    conn.execute(
+        "DELETE FROM forms WHERE pokemon_id IN (SELECT id FROM pokemon WHERE species = ?1);",
+        rusqlite::params![pokemon],
+    )
+    .into_diagnostic()?;
+    conn.execute("DELETE FROM pokemon WHERE species = ?1;", rusqlite::params![pokemon])
+        .into_diagnostic()?;
+    if let Some(chain) = species.evolution_chain {
+        conn.execute("DELETE FROM evolutions WHERE chain = ?1;", rusqlite::params![chain])
+            .into_diagnostic()?;
+    }
+    for egg_group in &species.egg_groups {
+        conn.execute("DELETE FROM egg_groups WHERE name = ?1;", rusqlite::params![egg_group])
+            .into_diagnostic()?;
+    }
+    Ok(())
+}
+
+/// Fetch a pokemon from PokeAPI again, replacing what was cached about it.
+/// Nothing is removed from the cache until the new data has been fetched, and the old data is only replaced if all the new
+/// data is cached. Returns the name and id of the pokemon in the cache.
+pub fn refresh_pokemon(conn: &Connection, pokemon: &str) -> Result<(String, i32), Error> {
+    info(format!("Refreshing {pokemon}'s info...").as_str());
+    let pokemon_data = api_get_pokemon_data(&response_fetcher(conn, FetchMode::Online), pokemon, |_| true, |_| true)?
+        .ok_or(miette!("PokeAPI has no pokemon or species named '{}'", pokemon))?;
+    let tx = write_transaction(conn)?;
+    purge_pokemon_data(&tx, &pokemon_data.form.pokemon, &pokemon_data.species)?;
+    let cached = ingest_pokemon_data(&tx, &pokemon_data)?;
+    tx.commit().into_diagnostic()?;
+    Ok(cached)
+}
+
+/// Derive everything cached about a pokemon again from the stored responses, without the network, e.g. to fill in
+/// data that was added to the cache after the pokemon was fetched. The pokemon keeps the fetch time of its response.
+/// Returns `None` if no response is stored for the pokemon, e.g. because it was cached before responses were stored,
+/// and an error if a response it links to, e.g. its species, isn't stored.
+pub fn rebuild_pokemon(conn: &Connection, pokemon: &str) -> Result<Option<(String, i32)>, Error> {
+    // The pokemon may have been fetched by id, e.g. as the default form of a species, so find it by name
+    let mut stmt = conn
+        .prepare(
+            "SELECT route, fetched_at FROM responses WHERE route LIKE 'pokemon/%' AND json_extract(body, '$.name') = ?1 \
+            ORDER BY fetched_at DESC LIMIT 1;",
+        )
+        .into_diagnostic()?;
+    let Some((route, fetched_at)) = stmt
+        .query_row(rusqlite::params![pokemon], |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, i64>(1)?)))
+        .optional()
+        .into_diagnostic()?
+    else {
+        return Ok(None);
+    };
+
+    let fetcher = response_fetcher(conn, FetchMode::Offline);
+    let pokemon_data = api_get_pokemon_data(&fetcher, route.trim_start_matches("pokemon/"), |_| true, |_| true)?
+        .ok_or(miette!("The stored response for '{}' has no pokemon", pokemon))?;
+
+    let tx = write_transaction(conn)?;
+    purge_pokemon_data(&tx, &pokemon_data.form.pokemon, &pokemon_data.species)?;
+    let (pokemon, pokemon_id) = ingest_pokemon_data(&tx, &pokemon_data)?;
+    tx.execute(
+        "UPDATE pokemon SET fetched_at = ?2 WHERE id = ?1;",
+        rusqlite::params![pokemon_id, fetched_at],
+    )
+    .into_diagnostic()?;
+    tx.commit().into_diagnostic()?;
+    Ok(Some((pokemon, pokemon_id)))
+}
+
+/// Derive a cached move's details again from its stored response, without the network. The move keeps the fetch time
+/// of its response. Returns `false` if no response is stored for the move.
+pub fn rebuild_move_data(conn: &Connection, pk_move: &str) -> Result<bool> {
+    let Some(response) = fetch_response(conn, format!("move/{pk_move}").as_str())? else {
+        return Ok(false);
+    };
+    let fetcher = response_fetcher(conn, FetchMode::Offline);
+    let move_data = api_get_move(&fetcher, pk_move)?
+        .ok_or(miette!("The stored response for '{}' has no move", pk_move))?;
+    cache_move_data(conn, &move_data, &fetcher.take_responses())?;
+    conn.execute(
+        "UPDATE move_data SET fetched_at = ?2 WHERE name = ?1;",
+        rusqlite::params![pk_move, response.fetched_at],
+    )
+    .into_diagnostic()?;
+    Ok(true)
+}
+
+/// Derive a cached item again from its stored response, without the network. The item keeps the fetch time of its
+/// response. Returns `false` if no response is stored for the item.
+pub fn rebuild_item(conn: &Connection, item: &str) -> Result<bool> {
+    let Some(response) = fetch_response(conn, format!("item/{item}").as_str())? else {
+        return Ok(false);
+    };
+    let fetcher = response_fetcher(conn, FetchMode::Offline);
+    let poke_item = api_get_item(&fetcher, item)?
+        .ok_or(miette!("The stored response for '{}' has no item", item))?;
+    cache_item(conn, &poke_item, &fetcher.take_responses())?;
+    conn.execute(
+        "UPDATE items SET fetched_at = ?2 WHERE name = ?1;",
+        rusqlite::params![item, response.fetched_at],
+    )
+    .into_diagnostic()?;
+    Ok(true)
+}
+
+/// Fetch a move from PokeAPI again, replacing what was cached about it. Moves PokeAPI no longer has are left as they are.
+pub fn refresh_move_data(conn: &Connection, pk_move: &str) -> Result<()> {
+    info(format!("Refreshing {pk_move}'s info...").as_str());
+    let fetcher = response_fetcher(conn, FetchMode::Online);
+    match api_get_move(&fetcher, pk_move)? {
+        Some(move_data) => cache_move_data(conn, &move_data, &fetcher.take_responses()),
+        None => Ok(()),
+    }
+}
+
+/// Fetch an item from PokeAPI again, replacing what was cached about it. Items PokeAPI no longer has are left as they are.
+pub fn refresh_item(conn: &Connection, item: &str) -> Result<()> {
+    info(format!("Refreshing {item}'s info...").as_str());
+    let fetcher = response_fetcher(conn, FetchMode::Online);
+    match api_get_item(&fetcher, item)? {
+        Some(poke_item) => cache_item(conn, &poke_item, &fetcher.take_responses()),
+        None => Ok(()),
+    }
+}
+
+/// Open the cache, setting it up first if this is the first time it is being used.
+///
+/// Table creation is idempotent, so tables added since the cache was first created are set up as well.
+pub fn prepare_cache() -> Result<Connection> {
+    let cache_exists = is_cache(); // Check for cache's existence before opening connection. Creating the conn object automatically initializes db on disk if it doesn't exit.
+    let conn = get_db_connection();
+    set_up_db(&conn)?;
+    if !cache_exists {
+        info("Setting up cache. This will only happen once!");
+        initialize_cache_data(&conn)?;
+    }
+    Ok(conn)
 }
 
 /// get and cache all misc data that is not linked to a specific pokemon
 pub fn initialize_cache_data(conn: &Connection) -> Result<()> {
-    cache_balls(conn, api_get_balls())
+    let fetcher = response_fetcher(conn, FetchMode::Online);
+    let balls = api_get_balls(&fetcher)?;
+    cache_balls(conn, balls, &fetcher.take_responses())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pikachu() -> PokemonData {
+        PokemonData {
+            species: PokeSpecies {
+                name: String::from("pikachu"),
+                is_legendary: false,
+                is_mythical: false,
+                is_baby: false,
+                gender_rate: Some(4),
+                egg_groups: vec![String::from("ground"), String::from("fairy")],
+                evolution_chain: Some(10),
+                generation: Some(Generation::GEN1),
+                varieties: vec![String::from("pikachu")],
+            },
+            evolution_chain: vec![PokeEvolution {
+                chain: 10,
+                species: String::from("pikachu"),
+                evolves_from: Some(String::from("pichu")),
+                min_level: None,
+            }],
+            egg_groups: vec![PokeEggGroup {
+                name: String::from("ground"),
+                species: vec![String::from("pikachu")],
+            }],
+            form: PokeForm {
+                species: String::from("pikachu"),
+                pokemon: String::from("pikachu"),
+                is_default: true,
+                generation: Some(Generation::GEN1),
+            },
+            moves: vec![
+                PokeMove {
+                    name: String::from("thunder-shock"),
+                    generations: HashSet::from([MoveLearnMethod {
+                        method: LearnMethod::LevelUp,
+                        level_learned_at: Some(1),
+                        generation: Generation::GEN1,
+                    }]),
+                },
+                PokeMove {
+                    name: String::from("thunderbolt"),
+                    generations: HashSet::from([MoveLearnMethod {
+                        method: LearnMethod::Machine,
+                        level_learned_at: None,
+                        generation: Generation::GEN1,
+                    }]),
+                },
+            ],
+            abilities: vec![PokeAbility {
+                name: String::from("static"),
+                slot: 1,
+                is_hidden: false,
+            }],
+            types: vec![PokeType::Electric],
+            base_stats: HashMap::from([(String::from("speed"), 90)]),
+            responses: Vec::new(),
+        }
+    }
+
+    fn count_rows(conn: &Connection, table: &str) -> i64 {
+        conn.query_row(format!("SELECT COUNT(*) FROM {table};").as_str(), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn failed_ingest_caches_nothing() {
+        let conn = Connection::open_in_memory().unwrap();
+        set_up_db(&conn).unwrap();
+        // Fail partway through, after the pokemon and its moves have been inserted
+        conn.execute_batch(
+            "CREATE TRIGGER fail_abilities BEFORE INSERT ON abilities BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+        )
+        .unwrap();
+
+        assert!(cache_pokemon_data(&conn, &pikachu()).is_err());
+        assert!(!is_species_cached(&conn, "pikachu"));
+        for table in ["pokemon", "species", "varieties", "evolutions", "egg_groups", "forms", "moves", "abilities", "types", "base_stats"] {
+            assert_eq!(count_rows(&conn, table), 0, "{table} should be empty");
+        }
+
+        // Nothing was left behind, so caching it again works
+        conn.execute_batch("DROP TRIGGER fail_abilities;").unwrap();
+        let (pokemon, pokemon_id) = cache_pokemon_data(&conn, &pikachu()).unwrap();
+        assert_eq!(pokemon, "pikachu");
+        assert_eq!(fetch_learnset(&conn, pokemon_id).unwrap().len(), 2);
+        assert_eq!(fetch_abilities(&conn, pokemon_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn duplicate_species_keep_their_forms() {
+        let conn = Connection::open_in_memory().unwrap();
+        set_up_db(&conn).unwrap();
+        let (_, pokemon_id) = cache_pokemon_data(&conn, &pikachu()).unwrap();
+
+        // A cache from before the unique indexes, where another process cached the species again along with a form
+        conn.execute_batch("DROP INDEX species_name; PRAGMA user_version = 3;").unwrap();
+        conn.execute("INSERT INTO species (name) VALUES ('pikachu');", ()).unwrap();
+        let duplicate_id = conn.last_insert_rowid();
+        conn.execute(
+            "UPDATE forms SET species_id = ?1 WHERE pokemon_id = ?2;",
+            rusqlite::params![duplicate_id, pokemon_id],
+        )
+        .unwrap();
+
+        set_up_db(&conn).unwrap();
+        assert_eq!(count_rows(&conn, "species"), 1);
+        assert_eq!(fetch_species_forms(&conn, "pikachu").unwrap().len(), 1);
+        assert_eq!(fetch_schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
 }
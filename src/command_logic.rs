@@ -1,11 +1,29 @@
-use crate::cache::{del_cache_on_disk, get_db_connection, initialize_cache_data, is_cache, set_up_db};
-use crate::console::{info, success};
-use crate::spec::PokeSpecBuilder;
-use crate::{CacheCommands, Commands};
-use miette::{IntoDiagnostic, Result};
+use crate::abilities::check_hidden_ability;
+use crate::api::evolution::PokeEvolution;
+use crate::bundle::{export_cache, import_cache};
+use crate::api::move_data::PokeMovePastValues;
+use crate::cache::{
+    del_cache_on_disk, fetch_abilities, fetch_cached_items, fetch_cached_move_data, fetch_cached_pokemon, fetch_base_stats, fetch_evolution_chain, fetch_form, fetch_learnset, fetch_species_details,
+    fetch_species_forms, fetch_stale_resources, fetch_types, get_and_cache_move_data, is_item_stale, is_move_cached, is_move_data_stale,
+    is_pokemon_stale, prepare_cache, rebuild_item, rebuild_move_data, rebuild_pokemon, refresh_item, refresh_move_data, refresh_pokemon, resolve_pokemon,
+};
+use crate::config::load_config;
+use crate::console::{err, info, success};
+use crate::moveset::MovesetStrategy;
+use crate::names::{display_name, to_slug};
+use crate::output::{print_json, render_table, OutputFormat};
+use crate::prefetch::{prefetch, prefetch_names, PrefetchSource};
+use crate::spec::{check_moveset, nature_effect, MovesetOwner, PokeSpecBuilder, StatSpreadBuilder};
+use crate::stat_calc::{possible_ivs, StatSnapshot, SUMMARY_STAT_ORDER};
+use crate::spread::SpreadStrategy;
+use crate::{CacheCommands, Commands, GenerateArgs};
+use itertools::Itertools;
+use miette::{miette, IntoDiagnostic, Result};
+use num_traits::ToPrimitive;
+use serde_json::json;
 use rusqlite::fallible_iterator::FallibleIterator;
-use std::collections::HashSet;
-use crate::enums::Gender;
+use rusqlite::Connection;
+use crate::enums::{Gender, Generation, LearnMethod, PokeType, TeraType};
 
 /// A trait that defines the interface for executing command logic
 pub trait CommandLogic {
@@ -30,43 +48,59 @@ impl CommandLogic for Generate {
     /// - sid
     /// - gender
     /// - nature
-    /// - ivs
-    /// - evs
+    /// - ivs (see: `iv_strategy`)
+    /// - evs (see: `ev_strategy`)
+    /// - moveset (see: `moveset_strategy`)
     ///
     /// Optional Args with No Default
     /// - nickname
-    /// - moveset
     fn execute(&self, args: Commands) -> Result<()> {
         // TODO: Get ability or random ability
         match &args {
-            Commands::Generate {
-                species,
-                ability,
-                level,
-                nickname,
-                shiny,
-                ot,
-                tid,
-                sid,
-                gender,
-                ball,
-                nature,
-                ivatk,
-                ivspatk,
-                ivdef,
-                ivspdef,
-                ivspd,
-                ivhp,
-                evatk,
-                evspatk,
-                evdef,
-                evspdef,
-                evspd,
-                evhp,
-                moveset,
-                generation,
-            } => {
+            Commands::Generate(generate_args) => {
+                let GenerateArgs {
+                    species,
+                    form,
+                    ability,
+                    level,
+                    nickname,
+                    shiny,
+                    ot,
+                    tid,
+                    sid,
+                    gender,
+                    ball,
+                    item,
+                    nature,
+                    ivatk,
+                    ivspatk,
+                    ivdef,
+                    ivspdef,
+                    ivspd,
+                    ivhp,
+                    evatk,
+                    evspatk,
+                    evdef,
+                    evspdef,
+                    evspd,
+                    evhp,
+                    iv_strategy,
+                    ev_strategy,
+                    moveset,
+                    moveset_strategy,
+                    pp_ups,
+                    hidden_power,
+                    tera_type,
+                    dynamax_level,
+                    gigantamax,
+                    generation,
+                    game,
+                    refresh,
+                } = generate_args.as_ref();
                 let mut spec_builder = PokeSpecBuilder::new(species);
+                if let Some(form) = form {
+                    spec_builder.form(form);
+                }
                 if ivatk.is_some() {
                     spec_builder.ivs().atk(ivatk.unwrap());
                 }
@@ -103,9 +137,14 @@ impl CommandLogic for Generate {
                 if evhp.is_some() {
                     spec_builder.evs().hp(evhp.unwrap());
                 }
+                spec_builder.ivs().strategy(SpreadStrategy::try_from(iv_strategy.as_str())?);
+                spec_builder.evs().strategy(SpreadStrategy::try_from(ev_strategy.as_str())?);
                 if ability.is_some() {
                     spec_builder.ability(ability.clone().unwrap().as_str());
                 }
+                if let Some(item) = item {
+                    spec_builder.item(item);
+                }
                 if nature.is_some() {
                     spec_builder.nature(nature.clone().unwrap().as_str());
                 }
@@ -113,20 +152,68 @@ impl CommandLogic for Generate {
                     spec_builder.nickname(nickname.clone().unwrap().as_str());
                 }
                 spec_builder.ot(ot).tid(tid.unwrap_or(0)).sid(sid.unwrap_or(0)); // TODO: Implement TID/SID pairing
-                spec_builder.move_set(HashSet::from_iter(moveset.clone()));
+                spec_builder.move_set(moveset.clone());
+                spec_builder.moveset_strategy(MovesetStrategy::try_from(moveset_strategy.as_str())?);
+                spec_builder.pp_ups(pp_ups.clone());
                 spec_builder.level(*level).shiny(*shiny).ball(ball.clone().as_str());
                 if gender.is_some() {
                     spec_builder.gender(Gender::try_from(gender.clone().unwrap().as_str())?);
                 }
+                let generation = match generation {
+                    Some(generation) => Some(
+                        Generation::parse(generation.to_string().as_str())
+                            .ok_or(miette!("'{}' is not a valid generation!", generation))?,
+                    ),
+                    None => None,
+                };
+                if let Some(game) = game {
+                    let game_generation = Generation::from_version_group(&to_slug(game))
+                        .ok_or(miette!("'{}' is not a valid game!", game))?;
+                    if generation.as_ref().is_some_and(|generation| *generation != game_generation) {
+                        return Err(miette!(
+                            "{} is not in {}",
+                            display_name(&to_slug(game)),
+                            Into::<String>::into(generation.unwrap())
+                        ));
+                    }
+                    spec_builder.game(game).generation(game_generation);
+                }
+                if let Some(generation) = generation {
+                    spec_builder.generation(generation);
+                }
+                if let Some(hidden_power) = hidden_power {
+                    spec_builder.hidden_power(PokeType::try_from(hidden_power.as_str())?);
+                }
+                if let Some(tera_type) = tera_type {
+                    spec_builder.tera_type(TeraType::try_from(tera_type.as_str())?);
+                }
+                if let Some(dynamax_level) = dynamax_level {
+                    spec_builder.dynamax_level(*dynamax_level);
+                }
+                spec_builder.gigantamax(*gigantamax);
+
+                let conn = prepare_cache()?;
 
-                let cache_exists = is_cache();  // Check for cache's existence before opening connection. Creating the conn object automatically initializes db on disk if it doesn't exit.
-                let conn = get_db_connection();
-                if !cache_exists {
-                    info("Setting up cache. This will only happen once!");
-                    set_up_db(&conn).expect("Unable to set up cache!");
-                    initialize_cache_data(&conn)?;
+                // Refresh before building, so the spec is validated against the refreshed data
+                if *refresh {
+                    let ttl_days = load_config()?.cache_ttl_days;
+                    let form = form.as_ref().map(|form| to_slug(form));
+                    let (pokemon, pokemon_id) = resolve_pokemon(&conn, &to_slug(species), form.as_deref())?;
+                    if is_pokemon_stale(&conn, pokemon_id, ttl_days)? {
+                        refresh_pokemon(&conn, &pokemon)?;
+                    }
+                    for pk_move in moveset.iter().map(|pk_move| to_slug(pk_move)) {
+                        if is_move_data_stale(&conn, &pk_move, ttl_days)? {
+                            refresh_move_data(&conn, &pk_move)?;
+                        }
+                    }
+                    if let Some(item) = item.as_ref().map(|item| to_slug(item))
+                        && is_item_stale(&conn, &item, ttl_days)?
+                    {
+                        refresh_item(&conn, &item)?;
+                    }
                 }
-                
+
                 let spec = spec_builder.build();
                 success(format!("{}", spec?).as_str());
                 Ok(())
@@ -136,6 +223,607 @@ impl CommandLogic for Generate {
     }
 }
 
+pub struct IvCalc;
+
+impl CommandLogic for IvCalc {
+    /// Work out the range of possible IVs for each stat from one or more summary screen snapshots.
+    /// See: README.md for more.
+    fn execute(&self, args: Commands) -> Result<()> {
+        match &args {
+            Commands::Ivcalc {
+                species,
+                level,
+                nature,
+                stats,
+                evatk,
+                evspatk,
+                evdef,
+                evspdef,
+                evspd,
+                evhp,
+            } => {
+                if nature_effect(nature).is_none() {
+                    return Err(miette!("'{}' is not a valid nature!", nature));
+                }
+
+                if level.len() != stats.len() {
+                    return Err(miette!(
+                        "Got {} levels but {} sets of stats. Each --level needs a matching --stats!",
+                        level.len(),
+                        stats.len()
+                    ));
+                }
+
+                let mut snapshots: Vec<StatSnapshot> = Vec::new();
+                for (level, observed) in level.iter().zip(stats) {
+                    let values = observed
+                        .split(',')
+                        .map(|value| value.trim().parse::<u16>())
+                        .collect::<Result<Vec<u16>, _>>()
+                        .into_diagnostic()?;
+                    if values.len() != SUMMARY_STAT_ORDER.len() {
+                        return Err(miette!(
+                            "'{}' must list six stats, in the order hp,atk,def,spatk,spdef,spd",
+                            observed
+                        ));
+                    }
+
+                    snapshots.push(StatSnapshot {
+                        level: *level,
+                        stats: SUMMARY_STAT_ORDER.iter().map(|stat| stat.to_string()).zip(values).collect(),
+                    });
+                }
+
+                // Build the EVs as a spread to validate them, treating any that weren't given as 0
+                let mut ev_builder = StatSpreadBuilder::evs();
+                ev_builder.strategy(SpreadStrategy::Zero);
+                if let Some(ev) = evatk {
+                    ev_builder.atk(*ev);
+                }
+                if let Some(ev) = evdef {
+                    ev_builder.def(*ev);
+                }
+                if let Some(ev) = evspatk {
+                    ev_builder.spatk(*ev);
+                }
+                if let Some(ev) = evspdef {
+                    ev_builder.spdef(*ev);
+                }
+                if let Some(ev) = evspd {
+                    ev_builder.spd(*ev);
+                }
+                if let Some(ev) = evhp {
+                    ev_builder.hp(*ev);
+                }
+                let evs = ev_builder.build()?;
+
+                let conn = prepare_cache()?;
+                let (_, species_id) = resolve_pokemon(&conn, &to_slug(species), None)?;
+                let base_stats = fetch_base_stats(&conn, species_id)?;
+                if base_stats.is_empty() {
+                    return Err(miette!(
+                        "No base stats are cached for {}. Clear the cache with `cache clear` and try again.",
+                        species
+                    ));
+                }
+
+                for stat in SUMMARY_STAT_ORDER {
+                    let ivs = possible_ivs(stat, base_stats[stat], evs.stats()[stat], nature, &snapshots);
+                    match (ivs.first(), ivs.last()) {
+                        (Some(min), Some(max)) if min == max => success(format!("{stat}: {min}").as_str()),
+                        (Some(min), Some(max)) => success(format!("{stat}: {min}-{max}").as_str()),
+                        _ => err(format!("{stat}: no IV matches the observed stats").as_str()),
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct Info;
+
+/// Describe a species' chance of each gender, given its gender rate in eighths female (or -1 if genderless)
+fn format_gender_rate(gender_rate: i8) -> String {
+    match gender_rate {
+        -1 => String::from("genderless"),
+        0 => String::from("100% male"),
+        8 => String::from("100% female"),
+        _ => {
+            let female = gender_rate as f64 * 12.5;
+            format!("{}% male, {}% female", 100.0 - female, female)
+        }
+    }
+}
+
+/// Every path through an evolution chain from its first stage. A branching chain like Eevee's has one path per branch.
+fn evolution_paths(chain: &[PokeEvolution]) -> Vec<Vec<&PokeEvolution>> {
+    let mut paths: Vec<Vec<&PokeEvolution>> = Vec::new();
+    let mut stack: Vec<Vec<&PokeEvolution>> = chain
+        .iter()
+        .filter(|evolution| evolution.evolves_from.is_none())
+        .rev()
+        .map(|evolution| vec![evolution])
+        .collect();
+    while let Some(path) = stack.pop() {
+        let last = path[path.len() - 1];
+        let next_stages: Vec<&PokeEvolution> = chain
+            .iter()
+            .filter(|evolution| evolution.evolves_from.as_deref() == Some(last.species.as_str()))
+            .sorted_by_key(|evolution| evolution.species.clone())
+            .collect();
+        if next_stages.is_empty() {
+            paths.push(path);
+            continue;
+        }
+        // Push in reverse so that branches are walked in name order
+        for next_stage in next_stages.into_iter().rev() {
+            let mut next_path = path.clone();
+            next_path.push(next_stage);
+            stack.push(next_path);
+        }
+    }
+    paths
+}
+
+/// Summarize one path through an evolution chain, e.g. `Bulbasaur -> Ivysaur (lv. 16) -> Venusaur (lv. 32)`
+fn format_evolution_path(path: &[&PokeEvolution]) -> String {
+    path.iter()
+        .map(|evolution| match evolution.min_level {
+            Some(min_level) if evolution.evolves_from.is_some() => {
+                format!("{} (lv. {min_level})", display_name(&evolution.species))
+            }
+            _ => display_name(&evolution.species),
+        })
+        .join(" -> ")
+}
+
+impl CommandLogic for Info {
+    /// Show the basics of a species from the cache, fetching it first if needed
+    fn execute(&self, args: Commands) -> Result<()> {
+        match &args {
+            Commands::Info { species, form, format } => {
+                let format = OutputFormat::try_from(format.as_str())?;
+                let conn = prepare_cache()?;
+                let form = form.as_ref().map(|form| to_slug(form));
+                let (pokemon, pokemon_id) = resolve_pokemon(&conn, &to_slug(species), form.as_deref())?;
+
+                // Species-level details live under the species name, which differs from the pokemon's for most forms
+                let species = fetch_form(&conn, pokemon_id)?.map_or(to_slug(species), |form| form.species);
+                let details = fetch_species_details(&conn, &species)?;
+                let types = fetch_types(&conn, pokemon_id)?;
+                let base_stats = fetch_base_stats(&conn, pokemon_id)?;
+                let abilities = fetch_abilities(&conn, pokemon_id)?;
+                let evolutions = match details.as_ref().and_then(|details| details.evolution_chain) {
+                    Some(chain) => fetch_evolution_chain(&conn, chain)?,
+                    None => Vec::new(),
+                };
+                // Species cached before varieties were tracked only know of the forms that have been cached
+                let forms = match details.as_ref().filter(|details| !details.varieties.is_empty()) {
+                    Some(details) => details.varieties.clone(),
+                    None => fetch_species_forms(&conn, &species)?
+                        .into_iter()
+                        .sorted_by_key(|form| !form.is_default)
+                        .map(|form| form.pokemon)
+                        .collect(),
+                };
+                let generation = details.as_ref().and_then(|details| details.generation.clone());
+                let gender_rate = details.as_ref().and_then(|details| details.gender_rate);
+                let egg_groups = details.as_ref().map_or(Vec::new(), |details| details.egg_groups.clone());
+
+                if format == OutputFormat::Json {
+                    print_json(&json!({
+                        "name": pokemon,
+                        "species": species,
+                        "generation": generation.as_ref().and_then(|generation| generation.to_i32()),
+                        "types": types.iter().map(|poke_type| poke_type.to_string()).collect::<Vec<String>>(),
+                        "base_stats": base_stats,
+                        "abilities": abilities
+                            .iter()
+                            .map(|ability| json!({"name": ability.name, "slot": ability.slot, "hidden": ability.is_hidden}))
+                            .collect::<Vec<serde_json::Value>>(),
+                        "gender_rate": gender_rate,
+                        "egg_groups": egg_groups,
+                        "evolutions": evolutions
+                            .iter()
+                            .map(|evolution| json!({
+                                "species": evolution.species,
+                                "evolves_from": evolution.evolves_from,
+                                "min_level": evolution.min_level,
+                            }))
+                            .collect::<Vec<serde_json::Value>>(),
+                        "forms": forms,
+                    }));
+                    return Ok(());
+                }
+
+                let unknown = || String::from("-");
+                let mut buffer: Vec<String> = vec![match &generation {
+                    Some(generation) => format!("{} ({})", display_name(&pokemon), Into::<String>::into(generation.clone())),
+                    None => display_name(&pokemon),
+                }];
+                buffer.push(format!("\ttypes: {}", types.iter().join(", ")));
+                if base_stats.is_empty() {
+                    buffer.push(format!("\tbase stats: {}", unknown()));
+                } else {
+                    buffer.push(format!(
+                        "\tbase stats: {} ({} total)",
+                        SUMMARY_STAT_ORDER
+                            .iter()
+                            .map(|stat| format!("{} {stat}", base_stats.get(*stat).copied().unwrap_or(0)))
+                            .join(", "),
+                        base_stats.values().sum::<u16>()
+                    ));
+                }
+                buffer.push(format!(
+                    "\tabilities: {}",
+                    abilities
+                        .iter()
+                        .map(|ability| if ability.is_hidden {
+                            format!("{} (hidden)", display_name(&ability.name))
+                        } else {
+                            display_name(&ability.name)
+                        })
+                        .join(", ")
+                ));
+                buffer.push(format!("\tgender: {}", gender_rate.map_or(unknown(), format_gender_rate)));
+                buffer.push(format!(
+                    "\tegg groups: {}",
+                    if egg_groups.is_empty() { unknown() } else { egg_groups.iter().map(|egg_group| display_name(egg_group)).join(", ") }
+                ));
+                let paths = evolution_paths(&evolutions);
+                if paths.is_empty() {
+                    buffer.push(format!("\tevolutions: {}", unknown()));
+                } else {
+                    buffer.push(String::from("\tevolutions:"));
+                    for path in &paths {
+                        buffer.push(format!("\t\t- {}", format_evolution_path(path)));
+                    }
+                }
+                buffer.push(format!("\tforms: {}", forms.iter().map(|form| display_name(form)).join(", ")));
+
+                success(buffer.join("\n").as_str());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct Learnset;
+
+/// A single way of learning a move, and every generation it could be learned that way in
+struct LearnsetEntry {
+    name: String,
+    method: LearnMethod,
+    level: Option<u8>,
+    generations: Vec<Generation>,
+}
+
+/// Collapse a sorted list of generations into ranges, e.g. Gens 1, 2, 3, 4, 7, and 8 become `1-4, 7-8`
+fn format_generations(generations: &[Generation]) -> String {
+    let mut ranges: Vec<(i32, i32)> = Vec::new();
+    for generation in generations.iter().filter_map(|generation| generation.to_i32()) {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == generation => *end = generation,
+            _ => ranges.push((generation, generation)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| if start == end { start.to_string() } else { format!("{start}-{end}") })
+        .join(", ")
+}
+
+impl CommandLogic for Learnset {
+    /// List the cached learnset of a species, fetching it first if needed
+    fn execute(&self, args: Commands) -> Result<()> {
+        match &args {
+            Commands::Learnset { species, form, generation, method, max_level, format } => {
+                let format = OutputFormat::try_from(format.as_str())?;
+                let generation = match generation {
+                    Some(generation) => Some(
+                        Generation::parse(generation.to_string().as_str())
+                            .ok_or(miette!("'{}' is not a valid generation!", generation))?,
+                    ),
+                    None => None,
+                };
+                let method = match method {
+                    Some(method) => Some(
+                        LearnMethod::from(to_slug(method).as_str())
+                            .ok_or(miette!("'{}' is not a valid learn method!", method))?,
+                    ),
+                    None => None,
+                };
+
+                let conn = prepare_cache()?;
+                let form = form.as_ref().map(|form| to_slug(form));
+                let (pokemon, pokemon_id) = resolve_pokemon(&conn, &to_slug(species), form.as_deref())?;
+
+                // Group each move's methods by how and at what level it is learned, so each row lists its generations
+                let mut entries: Vec<LearnsetEntry> = Vec::new();
+                for pk_move in fetch_learnset(&conn, pokemon_id)? {
+                    let grouped = pk_move
+                        .generations
+                        .iter()
+                        .filter(|learn| generation.as_ref().is_none_or(|generation| learn.generation == *generation))
+                        .filter(|learn| method.as_ref().is_none_or(|method| learn.method == *method))
+                        .filter(|learn| match (max_level, learn.level_learned_at) {
+                            (Some(max_level), Some(level)) if learn.method == LearnMethod::LevelUp => level <= *max_level,
+                            _ => true,
+                        })
+                        // PokeAPI gives a level of 0 for methods that don't involve one
+                        .into_group_map_by(|learn| {
+                            let level = learn.level_learned_at.filter(|_| learn.method == LearnMethod::LevelUp);
+                            (learn.method.clone(), level)
+                        });
+                    for ((method, level), learns) in grouped {
+                        entries.push(LearnsetEntry {
+                            name: pk_move.name.clone(),
+                            method,
+                            level,
+                            generations: learns.into_iter().map(|learn| learn.generation.clone()).sorted().collect(),
+                        });
+                    }
+                }
+                // Level-up moves first, in the order they are learned, then everything else by method
+                entries.sort_by_key(|entry| {
+                    (
+                        entry.method != LearnMethod::LevelUp,
+                        entry.method.to_string(),
+                        entry.level,
+                        entry.name.clone(),
+                        entry.generations.first().cloned(),
+                    )
+                });
+
+                if format == OutputFormat::Json {
+                    print_json(&json!({
+                        "name": pokemon,
+                        "moves": entries
+                            .iter()
+                            .map(|entry| json!({
+                                "move": entry.name,
+                                "method": entry.method.to_string(),
+                                "level": entry.level,
+                                "generations": entry
+                                    .generations
+                                    .iter()
+                                    .filter_map(|generation| generation.to_i32())
+                                    .collect::<Vec<i32>>(),
+                            }))
+                            .collect::<Vec<serde_json::Value>>(),
+                    }));
+                    return Ok(());
+                }
+
+                if entries.is_empty() {
+                    err(format!("No moves of {} match the given filters", display_name(&pokemon)).as_str());
+                    return Ok(());
+                }
+                let rows: Vec<Vec<String>> = entries
+                    .iter()
+                    .map(|entry| {
+                        vec![
+                            display_name(&entry.name),
+                            entry.method.to_string(),
+                            entry.level.map_or(String::from("-"), |level| level.to_string()),
+                            format_generations(&entry.generations),
+                        ]
+                    })
+                    .collect();
+                success(display_name(&pokemon).as_str());
+                success(render_table(&["Move", "Method", "Level", "Gens"], &rows).as_str());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct Search;
+
+/// Find every cached pokemon that could know all of the given moves, with the given ability, at a level.
+/// Uses the same legality checks as `generate`, so pre-evolution, event, and egg moves count, but only the cache is
+/// used: nothing is fetched from PokeAPI. Returns the name of each match, and whether the ability is its hidden ability.
+fn search_cached_pokemon(
+    conn: &Connection,
+    moves: &[String],
+    ability: Option<&str>,
+    level: u8,
+    generation: Option<&Generation>,
+) -> Result<Vec<(String, bool)>> {
+    let mut matches: Vec<(String, bool)> = Vec::new();
+    for (pokemon, pokemon_id) in fetch_cached_pokemon(conn)? {
+        let form = fetch_form(conn, pokemon_id)?;
+        if let (Some(generation), Some(form)) = (generation, &form)
+            && form.generation.as_ref().is_some_and(|introduced| introduced > generation)
+        {
+            continue;
+        }
+
+        let mut hidden_ability = false;
+        if let Some(ability) = ability {
+            match fetch_abilities(conn, pokemon_id)?.iter().find(|legal| legal.name == ability) {
+                Some(legal) if legal.is_hidden => {
+                    // Searches aren't for a ball, and a Poke Ball can hold any ability
+                    if !check_hidden_ability(ability, generation, "poke-ball").is_empty() {
+                        continue;
+                    }
+                    hidden_ability = true;
+                }
+                Some(_) => {}
+                None => continue,
+            }
+        }
+
+        let species_details = match &form {
+            Some(form) => fetch_species_details(conn, &form.species)?,
+            None => None,
+        };
+        let owner = MovesetOwner { pokemon: &pokemon, species_id: pokemon_id, species_details: species_details.as_ref() };
+        if check_moveset(conn, &owner, moves, level, generation, true)?.is_empty() {
+            matches.push((pokemon, hidden_ability));
+        }
+    }
+    matches.sort();
+    Ok(matches)
+}
+
+impl CommandLogic for Search {
+    /// Find every cached pokemon that could know all of the given moves, with the given ability, at a level.
+    /// See `search_cached_pokemon`.
+    fn execute(&self, args: Commands) -> Result<()> {
+        match &args {
+            Commands::Search { moves, ability, level, generation, format } => {
+                let format = OutputFormat::try_from(format.as_str())?;
+                let generation = match generation {
+                    Some(generation) => Some(
+                        Generation::parse(generation.to_string().as_str())
+                            .ok_or(miette!("'{}' is not a valid generation!", generation))?,
+                    ),
+                    None => None,
+                };
+                if moves.is_empty() && ability.is_none() {
+                    return Err(miette!("Nothing to search for. Give at least one --move or an --ability!"));
+                }
+                let moves: Vec<String> = moves.iter().map(|pk_move| to_slug(pk_move)).collect();
+                let ability = ability.as_ref().map(|ability| to_slug(ability));
+
+                let conn = prepare_cache()?;
+                for pk_move in &moves {
+                    if !is_move_cached(&conn, pk_move)? {
+                        return Err(miette!(
+                            help = "Check the spelling, or cache a pokemon that learns it first",
+                            "No cached pokemon learns a move named '{}'",
+                            pk_move
+                        ));
+                    }
+                }
+
+                let matches = search_cached_pokemon(&conn, &moves, ability.as_deref(), *level, generation.as_ref())?;
+
+                if format == OutputFormat::Json {
+                    print_json(&json!(
+                        matches
+                            .iter()
+                            .map(|(pokemon, hidden_ability)| json!({"name": pokemon, "hidden_ability": hidden_ability}))
+                            .collect::<Vec<serde_json::Value>>()
+                    ));
+                    return Ok(());
+                }
+
+                if matches.is_empty() {
+                    err("No cached pokemon match. Pokemon that haven't been fetched yet can't be searched.");
+                    return Ok(());
+                }
+                let buffer: Vec<String> = matches
+                    .iter()
+                    .map(|(pokemon, hidden_ability)| {
+                        if *hidden_ability {
+                            format!("\t- {} (hidden ability)", display_name(pokemon))
+                        } else {
+                            format!("\t- {}", display_name(pokemon))
+                        }
+                    })
+                    .collect();
+                success(format!("{} matching pokemon:\n{}", matches.len(), buffer.join("\n")).as_str());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub struct MoveInfo;
+
+/// Describe how a move's values changed in a given generation, e.g. `power 95, accuracy 100`
+fn format_changes(past_values: &PokeMovePastValues) -> String {
+    let mut changes: Vec<String> = Vec::new();
+    if let Some(move_type) = past_values.move_type {
+        changes.push(format!("type {move_type}"));
+    }
+    if let Some(power) = past_values.power {
+        changes.push(format!("power {power}"));
+    }
+    if let Some(accuracy) = past_values.accuracy {
+        changes.push(format!("accuracy {accuracy}%"));
+    }
+    if let Some(pp) = past_values.pp {
+        changes.push(format!("PP {pp}"));
+    }
+    changes.join(", ")
+}
+
+impl CommandLogic for MoveInfo {
+    /// Show the details of a move, as it is now or as it was in a given generation
+    fn execute(&self, args: Commands) -> Result<()> {
+        match &args {
+            Commands::Move { name, generation } => {
+                let generation = match generation {
+                    Some(generation) => Some(
+                        Generation::parse(generation.to_string().as_str())
+                            .ok_or(miette!("'{}' is not a valid generation!", generation))?,
+                    ),
+                    None => None,
+                };
+
+                let conn = prepare_cache()?;
+                let move_data = get_and_cache_move_data(&conn, &to_slug(name))?
+                    .ok_or(miette!("PokeAPI has no move named '{}'", name))?;
+                if let (Some(generation), Some(introduced)) = (&generation, &move_data.generation)
+                    && introduced > generation
+                {
+                    return Err(miette!(
+                        "{} was introduced in {}, after {}",
+                        display_name(&move_data.name),
+                        Into::<String>::into(introduced.clone()),
+                        Into::<String>::into(generation.clone())
+                    ));
+                }
+
+                let shown = match &generation {
+                    Some(generation) => move_data.in_generation(generation),
+                    None => move_data.clone(),
+                };
+                let format_value = |value: Option<u8>| value.map_or(String::from("-"), |value| value.to_string());
+
+                let mut buffer: Vec<String> = vec![
+                    display_name(&shown.name),
+                    format!("\ttype: {}", shown.move_type.map_or(String::from("-"), |move_type| move_type.to_string())),
+                    format!("\tclass: {}", shown.damage_class.map_or(String::from("-"), |class| class.to_string())),
+                    format!("\tpower: {}", format_value(shown.power)),
+                    format!("\taccuracy: {}", shown.accuracy.map_or(String::from("-"), |accuracy| format!("{accuracy}%"))),
+                    format!("\tPP: {}", format_value(shown.pp)),
+                    format!("\tpriority: {:+}", shown.priority),
+                ];
+                if let Some(introduced) = &move_data.generation {
+                    buffer.push(format!("\tintroduced: {}", Into::<String>::into(introduced.clone())));
+                }
+
+                // Past values hold what a move was like before the generation that changed it
+                let mut past_values = move_data.past_values.clone();
+                past_values.sort_by_key(|past_values| past_values.generation.clone());
+                if !past_values.is_empty() {
+                    buffer.push(String::from("\tchanges:"));
+                    for past_values in &past_values {
+                        buffer.push(format!(
+                            "\t\t- before {}: {}",
+                            Into::<String>::into(past_values.generation.clone()),
+                            format_changes(past_values)
+                        ));
+                    }
+                }
+
+                success(buffer.join("\n").as_str());
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 pub struct Cache;
 
 impl CommandLogic for Cache {
@@ -151,9 +839,204 @@ impl CommandLogic for Cache {
                     }
                     CacheCommands::Purge { species } => Ok(()),
                     CacheCommands::Validate {} => Ok(()),
+                    CacheCommands::Refresh { species } => {
+                        let conn = prepare_cache()?;
+                        match species {
+                            Some(species) => {
+                                let (pokemon, _) = resolve_pokemon(&conn, &to_slug(species), None)?;
+                                refresh_pokemon(&conn, &pokemon)?;
+                                success(format!("Refreshed {}", display_name(&pokemon)).as_str());
+                            }
+                            None => {
+                                let ttl_days = load_config()?.cache_ttl_days;
+                                let (pokemon, moves, items) = fetch_stale_resources(&conn, ttl_days)?;
+                                for name in &pokemon {
+                                    refresh_pokemon(&conn, name)?;
+                                }
+                                for pk_move in &moves {
+                                    refresh_move_data(&conn, pk_move)?;
+                                }
+                                for item in &items {
+                                    refresh_item(&conn, item)?;
+                                }
+                                success(
+                                    format!(
+                                        "Refreshed {} pokemon, {} moves, and {} items older than {} days",
+                                        pokemon.len(),
+                                        moves.len(),
+                                        items.len(),
+                                        ttl_days
+                                    )
+                                    .as_str(),
+                                );
+                            }
+                        }
+                        Ok(())
+                    }
+                    CacheCommands::Rebuild { species } => {
+                        let conn = prepare_cache()?;
+                        if let Some(species) = species {
+                            let (pokemon, _) = resolve_pokemon(&conn, &to_slug(species), None)?;
+                            if rebuild_pokemon(&conn, &pokemon)?.is_none() {
+                                return Err(miette!(
+                                    help = "Use `cache refresh` to fetch it again",
+                                    "No response is stored for {}",
+                                    display_name(&pokemon)
+                                ));
+                            }
+                            success(format!("Rebuilt {} from stored responses", display_name(&pokemon)).as_str());
+                            return Ok(());
+                        }
+
+                        // Keep going past anything that can't be rebuilt, so one bad response doesn't hold up the rest
+                        let mut rebuilt = (0, 0, 0);
+                        let mut missing: Vec<String> = Vec::new();
+                        let mut failed: Vec<String> = Vec::new();
+                        for (pokemon, _) in fetch_cached_pokemon(&conn)? {
+                            match rebuild_pokemon(&conn, &pokemon) {
+                                Ok(Some(_)) => rebuilt.0 += 1,
+                                Ok(None) => missing.push(pokemon),
+                                Err(e) => failed.push(format!("{pokemon} ({e})")),
+                            }
+                        }
+                        for pk_move in fetch_cached_move_data(&conn)? {
+                            match rebuild_move_data(&conn, &pk_move) {
+                                Ok(true) => rebuilt.1 += 1,
+                                Ok(false) => missing.push(pk_move),
+                                Err(e) => failed.push(format!("{pk_move} ({e})")),
+                            }
+                        }
+                        for item in fetch_cached_items(&conn)? {
+                            match rebuild_item(&conn, &item) {
+                                Ok(true) => rebuilt.2 += 1,
+                                Ok(false) => missing.push(item),
+                                Err(e) => failed.push(format!("{item} ({e})")),
+                            }
+                        }
+
+                        success(
+                            format!(
+                                "Rebuilt {} pokemon, {} moves, and {} items from stored responses",
+                                rebuilt.0, rebuilt.1, rebuilt.2
+                            )
+                            .as_str(),
+                        );
+                        if !missing.is_empty() {
+                            err(format!(
+                                "No responses are stored for: {}. Use `cache refresh` to fetch them again.",
+                                missing.join(", ")
+                            )
+                            .as_str());
+                        }
+                        if !failed.is_empty() {
+                            err(format!("Couldn't rebuild: {}", failed.join(", ")).as_str());
+                        }
+                        Ok(())
+                    }
+                    CacheCommands::Export { file } => {
+                        let conn = prepare_cache()?;
+                        let header = export_cache(&conn, file)?;
+                        success(
+                            format!("Exported the cache to {} ({})", file.display(), header.describe()).as_str(),
+                        );
+                        Ok(())
+                    }
+                    CacheCommands::Import { file } => {
+                        let header = import_cache(file)?;
+                        success(
+                            format!("Imported the cache from {} ({})", file.display(), header.describe()).as_str(),
+                        );
+                        Ok(())
+                    }
+                    CacheCommands::Prefetch { all, generation, species_list, jobs } => {
+                        let source = match (all, generation, species_list) {
+                            (true, _, _) => PrefetchSource::All,
+                            (_, Some(generation), _) => PrefetchSource::Generation(
+                                Generation::parse(generation.to_string().as_str())
+                                    .ok_or(miette!("'{}' is not a valid generation!", generation))?,
+                            ),
+                            (_, _, Some(species_list)) => PrefetchSource::SpeciesList(species_list.clone()),
+                            _ => return Err(miette!("Give one of --all, --gen, or --species-list to prefetch!")),
+                        };
+
+                        let conn = prepare_cache()?;
+                        info("Listing pokemon to prefetch...");
+                        let names = prefetch_names(&source)?;
+                        let summary = prefetch(&conn, &names, *jobs)?;
+                        success(format!("Fetched {} pokemon. {} were already cached.", summary.fetched, summary.skipped).as_str());
+                        if !summary.missing.is_empty() {
+                            err(format!("PokeAPI has no pokemon or species named: {}", summary.missing.join(", ")).as_str());
+                        }
+                        Ok(())
+                    }
                 }
             }
             _ => Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::form::PokeForm;
+    use crate::api::pokemon::PokemonData;
+    use crate::api::pokemon_move::{MoveLearnMethod, PokeMove};
+    use crate::api::species::PokeSpecies;
+    use crate::cache::{cache_pokemon_data, set_up_db};
+    use std::collections::{HashMap, HashSet};
+
+    /// Cache a pokemon that learns a single move from a machine in a single generation
+    fn cache_machine_learner(conn: &Connection, pokemon: &str, pk_move: &str, generation: Generation) {
+        let pokemon_data = PokemonData {
+            species: PokeSpecies {
+                name: String::from(pokemon),
+                is_legendary: false,
+                is_mythical: false,
+                is_baby: false,
+                gender_rate: Some(4),
+                egg_groups: Vec::new(),
+                evolution_chain: None,
+                generation: Some(Generation::GEN1),
+                varieties: vec![String::from(pokemon)],
+            },
+            evolution_chain: Vec::new(),
+            egg_groups: Vec::new(),
+            form: PokeForm {
+                species: String::from(pokemon),
+                pokemon: String::from(pokemon),
+                is_default: true,
+                generation: Some(Generation::GEN1),
+            },
+            moves: vec![PokeMove {
+                name: String::from(pk_move),
+                generations: HashSet::from([MoveLearnMethod {
+                    method: LearnMethod::Machine,
+                    level_learned_at: None,
+                    generation,
+                }]),
+            }],
+            abilities: Vec::new(),
+            types: Vec::new(),
+            base_stats: HashMap::new(),
+            responses: Vec::new(),
+        };
+        cache_pokemon_data(conn, &pokemon_data).unwrap();
+    }
+
+    #[test]
+    fn search_only_matches_moves_learnable_in_the_generation() {
+        let conn = Connection::open_in_memory().unwrap();
+        set_up_db(&conn).unwrap();
+        cache_machine_learner(&conn, "sandslash", "earthquake", Generation::GEN3);
+        cache_machine_learner(&conn, "pikachu", "earthquake", Generation::GEN8);
+        let moves = vec![String::from("earthquake")];
+
+        let matches = search_cached_pokemon(&conn, &moves, None, 50, Some(&Generation::GEN3)).unwrap();
+        assert_eq!(matches, vec![(String::from("sandslash"), false)]);
+
+        // Moves can be transferred forward, but not back
+        let matches = search_cached_pokemon(&conn, &moves, None, 50, Some(&Generation::GEN8)).unwrap();
+        assert_eq!(matches, vec![(String::from("pikachu"), false), (String::from("sandslash"), false)]);
+    }
+}
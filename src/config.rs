@@ -0,0 +1,28 @@
+use figment::providers::{Env, Format, Json, Serialized};
+use figment::Figment;
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+
+/// Settings are read from this file, then overridden by environment variables prefixed with `POKESPECRS_`,
+/// e.g. `POKESPECRS_CACHE_TTL_DAYS=7`
+const CONFIG_FILE: &str = ".pokespecrs/config.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub cache_ttl_days: u64, // How long a cached resource stays fresh, before `--refresh` and `cache refresh` fetch it again
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { cache_ttl_days: 30 }
+    }
+}
+
+/// Load the settings, falling back to the defaults for any that aren't set
+pub fn load_config() -> Result<Config> {
+    Figment::from(Serialized::defaults(Config::default()))
+        .merge(Json::file(CONFIG_FILE))
+        .merge(Env::prefixed("POKESPECRS_"))
+        .extract()
+        .into_diagnostic()
+}
@@ -1,10 +1,11 @@
 use colored::Colorize;
 
+// Errors and progress messages go to stderr so they don't get mixed into output meant for scripts
 pub fn err(msg: &str) {
-    println!("{}", msg.red())
+    eprintln!("{}", msg.red())
 }
 pub fn info(msg: &str) {
-    println!("{}", msg.yellow())
+    eprintln!("{}", msg.yellow())
 }
 pub fn success(msg: &str) {
     println!("{}", msg.green())
@@ -1,5 +1,5 @@
-use crate::console::err;
 use miette::{miette, Result};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, FromPrimitive, ToPrimitive)]
 pub enum Gender {
@@ -34,7 +34,7 @@ impl Into<String> for Gender {
 
 
 
-#[derive(FromPrimitive, ToPrimitive, Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(FromPrimitive, ToPrimitive, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum Generation {
     GEN1 = 1,
     GEN2 = 2,
@@ -64,10 +64,14 @@ impl Into<String> for Generation {
 }
 
 impl Generation {
+    /// Check if a pokemon from this generation's games can be moved into the target generation's, keeping its moves.
+    /// Gen 1 and 2 pokemon can't reach Gens 3 through 6, as they only move forward through Gen 7's Virtual Console transfer.
+    pub fn can_transfer_to(&self, target: &Generation) -> bool {
+        self <= target && !(*self <= Generation::GEN2 && (Generation::GEN3..=Generation::GEN6).contains(target))
+    }
 
-    // Fake `from` function that returns a value, if one matches, from the enum.
-    // I cannot decide if its better to do this or implement a TryFrom with increased complexity of custom error types
-    pub fn parse(value: &str) -> Option<Self> {
+    /// The generation of a PokeAPI version group, e.g. `sword-shield` or `legends-arceus`
+    pub fn from_version_group(value: &str) -> Option<Self> {
         match value {
             "red-blue" => Some(Generation::GEN1),
             "yellow" => Some(Generation::GEN1),
@@ -92,6 +96,26 @@ impl Generation {
             "legends-arceus" => Some(Generation::GEN8),
             "brilliant-diamond-and-shining-pearl" => Some(Generation::GEN4),
             "scarlet-violet" => Some(Generation::GEN9),
+            _ => None,
+        }
+    }
+
+    // Fake `from` function that returns a value, if one matches, from the enum.
+    // I cannot decide if its better to do this or implement a TryFrom with increased complexity of custom error types
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Some(generation) = Generation::from_version_group(value) {
+            return Some(generation);
+        }
+        match value {
+            "generation-i" => Some(Generation::GEN1),
+            "generation-ii" => Some(Generation::GEN2),
+            "generation-iii" => Some(Generation::GEN3),
+            "generation-iv" => Some(Generation::GEN4),
+            "generation-v" => Some(Generation::GEN5),
+            "generation-vi" => Some(Generation::GEN6),
+            "generation-vii" => Some(Generation::GEN7),
+            "generation-viii" => Some(Generation::GEN8),
+            "generation-ix" => Some(Generation::GEN9),
             "1" => Some(Generation::GEN1),
             "2" => Some(Generation::GEN2),
             "3" => Some(Generation::GEN3),
@@ -106,12 +130,22 @@ impl Generation {
     }
 }
 
+/// Every way PokeAPI knows of for a pokemon to learn a move, plus event distributions, which PokeAPI doesn't track.
+/// The values are stored in the cache, so new methods must be added at the end.
 #[derive(Debug, Clone, ToPrimitive, FromPrimitive, Eq, Hash, PartialEq)]
 pub enum LearnMethod {
     Machine = 0,
     Egg = 1,
     Tutor = 2,
     LevelUp = 3,
+    StadiumSurfingPikachu = 4, // Surf for Pikachu, from Pokemon Stadium
+    LightBallEgg = 5,          // Volt Tackle for Pichu, by breeding a Pikachu or Raichu holding a Light Ball
+    ColosseumPurification = 6,
+    XdShadow = 7,
+    XdPurification = 8,
+    FormChange = 9,  // Moves learned by changing form, e.g. Rotom's appliance moves
+    ZygardeCube = 10,
+    Event = 11, // Moves only known by event distributions. See `events.rs`.
 }
 
 impl LearnMethod {
@@ -121,10 +155,15 @@ impl LearnMethod {
             "egg" => Some(LearnMethod::Egg),
             "tutor" => Some(LearnMethod::Tutor),
             "level-up" => Some(LearnMethod::LevelUp),
-            _ => {
-                err(format!("Invalid LearnMethod value: '{}'", value).as_str());
-                None
-            }
+            "stadium-surfing-pikachu" => Some(LearnMethod::StadiumSurfingPikachu),
+            "light-ball-egg" => Some(LearnMethod::LightBallEgg),
+            "colosseum-purification" => Some(LearnMethod::ColosseumPurification),
+            "xd-shadow" => Some(LearnMethod::XdShadow),
+            "xd-purification" => Some(LearnMethod::XdPurification),
+            "form-change" => Some(LearnMethod::FormChange),
+            "zygarde-cube" => Some(LearnMethod::ZygardeCube),
+            "event" => Some(LearnMethod::Event),
+            _ => None,
         }
     }
 
@@ -134,6 +173,129 @@ impl LearnMethod {
             LearnMethod::Egg => "egg".to_string(),
             LearnMethod::Tutor => "tutor".to_string(),
             LearnMethod::LevelUp => "level-up".to_string(),
+            LearnMethod::StadiumSurfingPikachu => "stadium-surfing-pikachu".to_string(),
+            LearnMethod::LightBallEgg => "light-ball-egg".to_string(),
+            LearnMethod::ColosseumPurification => "colosseum-purification".to_string(),
+            LearnMethod::XdShadow => "xd-shadow".to_string(),
+            LearnMethod::XdPurification => "xd-purification".to_string(),
+            LearnMethod::FormChange => "form-change".to_string(),
+            LearnMethod::ZygardeCube => "zygarde-cube".to_string(),
+            LearnMethod::Event => "event".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ToPrimitive, FromPrimitive, Eq, Hash, PartialEq)]
+pub enum PokeType {
+    Normal,
+    Fighting,
+    Flying,
+    Poison,
+    Ground,
+    Rock,
+    Bug,
+    Ghost,
+    Steel,
+    Fire,
+    Water,
+    Grass,
+    Electric,
+    Psychic,
+    Ice,
+    Dragon,
+    Dark,
+    Fairy,
+}
+
+/// A basic string-to-enum conversion
+impl TryFrom<&str> for PokeType {
+    type Error = miette::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "normal" => Ok(PokeType::Normal),
+            "fighting" => Ok(PokeType::Fighting),
+            "flying" => Ok(PokeType::Flying),
+            "poison" => Ok(PokeType::Poison),
+            "ground" => Ok(PokeType::Ground),
+            "rock" => Ok(PokeType::Rock),
+            "bug" => Ok(PokeType::Bug),
+            "ghost" => Ok(PokeType::Ghost),
+            "steel" => Ok(PokeType::Steel),
+            "fire" => Ok(PokeType::Fire),
+            "water" => Ok(PokeType::Water),
+            "grass" => Ok(PokeType::Grass),
+            "electric" => Ok(PokeType::Electric),
+            "psychic" => Ok(PokeType::Psychic),
+            "ice" => Ok(PokeType::Ice),
+            "dragon" => Ok(PokeType::Dragon),
+            "dark" => Ok(PokeType::Dark),
+            "fairy" => Ok(PokeType::Fairy),
+            _ => Err(miette!("'{}' is not a valid pokemon type!", value)),
+        }
+    }
+}
+
+impl Display for PokeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Whether a move deals physical or special damage, or none at all
+#[derive(Debug, Clone, Copy, ToPrimitive, FromPrimitive, Eq, Hash, PartialEq)]
+pub enum DamageClass {
+    Physical,
+    Special,
+    Status,
+}
+
+/// A basic string-to-enum conversion
+impl TryFrom<&str> for DamageClass {
+    type Error = miette::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "physical" => Ok(DamageClass::Physical),
+            "special" => Ok(DamageClass::Special),
+            "status" => Ok(DamageClass::Status),
+            _ => Err(miette!("'{}' is not a valid damage class!", value)),
+        }
+    }
+}
+
+impl Display for DamageClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A Tera type may be any of the regular types, or Stellar
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum TeraType {
+    Type(PokeType),
+    Stellar,
+}
+
+/// A basic string-to-enum conversion
+impl TryFrom<&str> for TeraType {
+    type Error = miette::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "stellar" => Ok(TeraType::Stellar),
+            _ => PokeType::try_from(value)
+                .map(TeraType::Type)
+                .map_err(|_| miette!("'{}' is not a valid tera type!", value)),
+        }
+    }
+}
+
+impl Display for TeraType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TeraType::Type(poke_type) => write!(f, "{}", poke_type),
+            TeraType::Stellar => write!(f, "Stellar"),
         }
     }
 }
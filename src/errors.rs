@@ -18,6 +18,26 @@ pub(crate) enum SpecErrors {
     #[diagnostic(help("EV values must be between 0 and 252"))]
     EvValueError { stat: String, value: String },
 
+    #[error("Spread strategy error! {strategy} cannot be used for {stat_type}s")]
+    #[diagnostic(help("`perfect-N` only applies to IVs, and `competitive` only applies to EVs"))]
+    IllegalSpreadStrategyError { strategy: String, stat_type: String },
+
+    #[error("Perfect IV error! Cannot guarantee {count} perfect IVs, only {available} stats are free")]
+    #[diagnostic(help("Un-pin some IVs or pin them to 31"))]
+    PerfectIvError { count: String, available: String },
+
+    #[error("Hidden Power type error: {hp_type}")]
+    #[diagnostic(help("Hidden Power can be any type except Normal and Fairy"))]
+    HiddenPowerTypeError { hp_type: String },
+
+    #[error("Hidden Power generation error: {generation}")]
+    #[diagnostic(help("Hidden Power's type only depends on IVs in Gens 2 through 7"))]
+    HiddenPowerGenerationError { generation: String },
+
+    #[error("Hidden Power IV error! No IV spread produces Hidden Power {hp_type} with the given IVs")]
+    #[diagnostic(help("Un-pin or change some of the IVs"))]
+    HiddenPowerIvError { hp_type: String },
+
     #[error("EV sum error!")]
     #[diagnostic(help("EV sum values must be between 0 and 510"))]
     EvSumError { ev_sum: String },
@@ -30,6 +50,38 @@ pub(crate) enum SpecErrors {
     #[diagnostic(help("This species cannot learn this move!"))]
     UnlearnableMoveError { species: String, pk_move: String },
 
+    #[error("Move not learnable in {generation}! {species}: {pk_move}")]
+    #[diagnostic(help("This species only learns this move in games it can't be transferred from into {generation}"))]
+    MoveNotInGenerationError { species: String, pk_move: String, generation: String },
+
+    #[error("Duplicate move: {pk_move}")]
+    #[diagnostic(help("A pokemon can only know each move once"))]
+    DuplicateMoveError { pk_move: String },
+
+    #[error("Too many moves! {count} moves were given")]
+    #[diagnostic(help("A pokemon can know at most four moves"))]
+    TooManyMovesError { count: String },
+
+    #[error("PP Up error! {count} PP Up counts were given for {moves} moves")]
+    #[diagnostic(help("Give at most one PP Up count per move, in slot order"))]
+    PpUpCountError { count: String, moves: String },
+
+    #[error("PP Up error! {pk_move}: {value}")]
+    #[diagnostic(help("Each move can have between 0 and 3 PP Ups"))]
+    PpUpValueError { pk_move: String, value: String },
+
+    #[error("PP Up error! {pk_move} cannot gain PP Ups in {generation}")]
+    #[diagnostic(help("From Gen 3, PP Ups can't be used on moves with 1 base PP, such as Sketch"))]
+    PpUpBasePpError { pk_move: String, generation: String },
+
+    #[error("Unknown move: {pk_move}")]
+    #[diagnostic(help("PokeAPI has no such move. Is it spelled correctly?"))]
+    UnknownMoveError { pk_move: String },
+
+    #[error("Move generation error! {pk_move} does not exist in {generation}")]
+    #[diagnostic(help("This move was introduced in a later generation"))]
+    MoveGenerationError { pk_move: String, generation: String },
+
     #[error(
         "Move level too low! {species}: lvl {level}, {pk_move}. Minimum learn level is {min_level}"
     )]
@@ -45,17 +97,81 @@ pub(crate) enum SpecErrors {
     #[diagnostic(help("This species cannot legally be obtained at such a low level!"))]
     LevelTooLowSpeciesError { species: String, level: String },
 
+    #[error("Form not available in {generation}: {pokemon}")]
+    #[diagnostic(help("This form was introduced in a later generation"))]
+    FormGenerationError { pokemon: String, generation: String },
+
     #[error("Illegal ability for species {species}: {ability}")]
     #[diagnostic()]
     IllegalAbilityError { species: String, ability: String },
 
+    #[error("Hidden ability not available in {generation}: {ability}")]
+    #[diagnostic(help("Hidden abilities were introduced in Gen 5"))]
+    HiddenAbilityGenerationError { ability: String, generation: String },
+
+    #[error("Hidden ability {ability} cannot be in a {ball} in {generation}")]
+    #[diagnostic(help("Before Gen 7, Pokemon with hidden abilities could not be in Apricorn Balls or Sport Balls"))]
+    HiddenAbilityBallError { ability: String, ball: String, generation: String },
+
     #[error("Illegal gender for species {species}: {gender}")]
     #[diagnostic()]
     IllegalGenderError { species: String, gender: String },
 
+    #[error("Unknown item: {item}")]
+    #[diagnostic(help("PokeAPI has no such item. Item names look like `leftovers` or `choice-scarf`"))]
+    UnknownItemError { item: String },
+
+    #[error("Item not available in {generation}: {item}")]
+    #[diagnostic()]
+    ItemGenerationError { item: String, generation: String },
+
+    #[error("Illegal item for species {species}: {item}")]
+    #[diagnostic(help("This item can only be held by specific species"))]
+    IllegalItemError { species: String, item: String },
+
+    #[error("{mechanic} is not available in {generation}")]
+    #[diagnostic(help("Terastallization is exclusive to Gen 9, and Dynamax and Gigantamax are exclusive to Sword and Shield"))]
+    MechanicGenerationError { mechanic: String, generation: String },
+
+    #[error("Dynamax level error: {level}")]
+    #[diagnostic(help("Dynamax levels must be between 0 and 10"))]
+    DynamaxLevelError { level: String },
+
+    #[error("Gigantamax error: {species}")]
+    #[diagnostic(help("This species has no Gigantamax form!"))]
+    GigantamaxError { species: String },
+
     #[error("Unknown type of Pokeball: {ball}")]
     #[diagnostic()]
-    UnknownBallError {ball: String}
+    UnknownBallError {ball: String},
+
+    #[error("Ball not available in {generation}: {ball}")]
+    #[diagnostic(help("This ball was introduced in a later generation"))]
+    BallGenerationError { ball: String, generation: String },
+
+    #[error("Illegal ball for species {species}: {ball}")]
+    #[diagnostic(help("This species cannot be in this ball, as {reason}"))]
+    IllegalBallError { species: String, ball: String, reason: String },
+
+    #[error("Breeding error! {species} cannot have egg moves")]
+    #[diagnostic(help("This species cannot breed, as {reason}"))]
+    UnbreedableSpeciesError { species: String, reason: String },
+
+    #[error("Egg move error! No breeding chain can pass {pk_move} to {species} in {generation}")]
+    #[diagnostic(help(
+        "A father sharing an egg group with the mother must know the move, either by learning it or from a father of its own"
+    ))]
+    NoFatherChainError { species: String, pk_move: String, generation: String },
+
+    #[error("Egg move error! No breeding chain of cached fathers can pass {moves} to {species} in {generation}")]
+    #[diagnostic(help(
+        "{unchecked} possible fathers aren't cached and were not fetched. Cache them with `cache prefetch` and try again"
+    ))]
+    UncheckedFathersError { species: String, moves: String, generation: String, unchecked: String },
+
+    #[error("Incompatible egg moves! No single father can pass {moves} to {species} in {generation}")]
+    #[diagnostic(help("Before Gen 6, only the father passes egg moves down, so he must know all of them at once"))]
+    IncompatibleEggMovesError { species: String, moves: String, generation: String },
 }
 
 #[derive(Debug, Diagnostic, Error, Clone)]
@@ -0,0 +1,82 @@
+use crate::api::pokemon_move::MoveLearnMethod;
+use crate::enums::{Generation, LearnMethod};
+use num_traits::FromPrimitive;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// A distribution of a pokemon that knew moves it can't otherwise learn
+#[derive(Debug, Clone, Deserialize)]
+struct EventDistribution {
+    species: String,
+    generation: i32,
+    level: u8,
+    moves: Vec<String>,
+}
+
+/// Event distributions with moves PokeAPI doesn't know about, as its learnsets only cover the games themselves.
+/// Each entry is named after the distribution it describes. Add new distributions there.
+static EVENTS_JSON: &str = include_str!("../data/events.json");
+
+/// Parse the event distributions the first time they are needed. They're shipped with PokeSpecRS, so if they can't be
+/// parsed it's a bug rather than something a user can fix.
+fn event_distributions() -> &'static [EventDistribution] {
+    static EVENT_DISTRIBUTIONS: OnceLock<Vec<EventDistribution>> = OnceLock::new();
+    EVENT_DISTRIBUTIONS.get_or_init(|| serde_json::from_str(EVENTS_JSON).expect("data/events.json is invalid"))
+}
+
+/// Retrieve every event distribution a pokemon could have learned a move from, as learn methods.
+/// A pokemon matches the distributions of its species, e.g. `genesect-douse` matches `genesect`.
+pub fn event_move_methods(pokemon: &str, pk_move: &str) -> HashSet<MoveLearnMethod> {
+    event_distributions()
+        .iter()
+        .filter(|event| event.species == pokemon || pokemon.starts_with(format!("{}-", event.species).as_str()))
+        .filter(|event| event.moves.iter().any(|event_move| event_move == pk_move))
+        .map(|event| MoveLearnMethod {
+            method: LearnMethod::Event,
+            level_learned_at: Some(event.level),
+            generation: Generation::from_i32(event.generation).unwrap(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_distribution_is_valid() {
+        for event in event_distributions() {
+            assert!(
+                Generation::from_i32(event.generation).is_some(),
+                "{} has no generation {}",
+                event.species,
+                event.generation
+            );
+            assert!(!event.moves.is_empty(), "{} has no moves", event.species);
+        }
+    }
+
+    #[test]
+    fn known_event_moves_are_found() {
+        let methods = event_move_methods("zigzagoon", "extreme-speed");
+        assert_eq!(
+            methods,
+            HashSet::from([MoveLearnMethod {
+                method: LearnMethod::Event,
+                level_learned_at: Some(5),
+                generation: Generation::GEN3,
+            }])
+        );
+
+        // Pichu was distributed with different moves in different generations
+        assert_eq!(event_move_methods("pichu", "dizzy-punch").len(), 1);
+        assert_eq!(event_move_methods("pichu", "surf").len(), 1);
+        assert_eq!(event_move_methods("pichu", "volt-tackle").iter().next().unwrap().generation, Generation::GEN4);
+
+        // Forms match the distributions of their species
+        assert_eq!(event_move_methods("genesect-douse", "shift-gear").len(), 1);
+        assert!(event_move_methods("pikachu", "v-create").is_empty());
+        assert!(event_move_methods("victini", "tackle").is_empty());
+    }
+}
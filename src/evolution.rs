@@ -0,0 +1,81 @@
+use crate::api::pokemon_move::MoveLearnMethod;
+use crate::api::species::PokeSpecies;
+use crate::cache::{fetch_cached_species_details, fetch_evolution_chain, fetch_move_methods, get_and_cache_species_details};
+use crate::enums::LearnMethod;
+use crate::events::event_move_methods;
+use miette::Result;
+use rusqlite::Connection;
+use std::collections::HashSet;
+
+/// A stage that a pokemon could have been at before evolving into its current species
+#[derive(Debug, Clone)]
+pub struct PreEvolution {
+    pub species: PokeSpecies,
+    pub pokemon_id: i32,
+    pub min_evolution_level: u8, // The lowest level the pokemon could have evolved out of this stage and into its current species at
+}
+
+/// Find every pre-evolution of a species, nearest first, fetching them from PokeAPI if needed unless `cache_only` is set.
+/// E.g. `charizard` has `charmeleon` (evolving at 36 or later) and `charmander` (evolving at 16, then 36, or later).
+pub fn pre_evolutions(conn: &Connection, species: &PokeSpecies, cache_only: bool) -> Result<Vec<PreEvolution>> {
+    let chain = match species.evolution_chain {
+        Some(chain) => fetch_evolution_chain(conn, chain)?,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut pre_evolutions: Vec<PreEvolution> = Vec::new();
+    let mut current = species.name.clone();
+    let mut min_evolution_level: u8 = 1;
+    while let Some(evolution) = chain.iter().find(|evolution| evolution.species == current)
+        && let Some(evolves_from) = &evolution.evolves_from
+    {
+        // Every evolution between the pre-evolution and the current species must have happened by now
+        min_evolution_level = min_evolution_level.max(evolution.min_level.unwrap_or(1));
+        current = evolves_from.clone();
+
+        let details = if cache_only {
+            fetch_cached_species_details(conn, evolves_from)?
+        } else {
+            get_and_cache_species_details(conn, evolves_from)?
+        };
+        match details {
+            Some((species, pokemon_id)) => pre_evolutions.push(PreEvolution {
+                species,
+                pokemon_id,
+                min_evolution_level,
+            }),
+            None => break,
+        }
+    }
+
+    Ok(pre_evolutions)
+}
+
+/// Retrieve the methods by which a pre-evolution learns a move, as they apply to the evolved pokemon.
+///
+/// A move learned by leveling up (or from an event) before evolving needs the pokemon to have reached both the level
+/// the move is learned at and every evolution level since, so the later of the two is used as the level it is learned at.
+pub fn inherited_move_methods(
+    conn: &Connection,
+    pre_evolution: &PreEvolution,
+    pk_move: &str,
+) -> Result<HashSet<MoveLearnMethod>> {
+    let mut methods = fetch_move_methods(conn, pre_evolution.pokemon_id, pk_move)?;
+    methods.extend(event_move_methods(&pre_evolution.species.name, pk_move));
+
+    Ok(methods
+        .into_iter()
+        .map(|method| match method.method {
+            LearnMethod::LevelUp | LearnMethod::Event => MoveLearnMethod {
+                level_learned_at: Some(
+                    method
+                        .level_learned_at
+                        .unwrap_or(0)
+                        .max(pre_evolution.min_evolution_level),
+                ),
+                ..method
+            },
+            _ => method,
+        })
+        .collect())
+}
@@ -0,0 +1,173 @@
+use crate::enums::{Generation, PokeType};
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{HiddenPowerGenerationError, HiddenPowerIvError, HiddenPowerTypeError};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// The types Hidden Power can take on, indexed by the result of the type formula
+static HIDDEN_POWER_TYPES: [PokeType; 16] = [
+    PokeType::Fighting,
+    PokeType::Flying,
+    PokeType::Poison,
+    PokeType::Ground,
+    PokeType::Rock,
+    PokeType::Bug,
+    PokeType::Ghost,
+    PokeType::Steel,
+    PokeType::Fire,
+    PokeType::Water,
+    PokeType::Grass,
+    PokeType::Electric,
+    PokeType::Psychic,
+    PokeType::Ice,
+    PokeType::Dragon,
+    PokeType::Dark,
+];
+
+/// The order in which Gen 3+ IVs contribute their bits to Hidden Power, from least to most significant
+static BIT_ORDER: [&str; 6] = ["hp", "atk", "def", "spd", "spatk", "spdef"];
+
+/// Hidden Power only exists (and depends on IVs) from Gen 2 through Gen 7
+pub fn has_hidden_power(generation: &Generation) -> bool {
+    (Generation::GEN2..=Generation::GEN7).contains(generation)
+}
+
+/// Calculate the type and base power of Hidden Power for a full IV spread.
+///
+/// Gen 2 derives Hidden Power from DVs, which are approximated here as half of the IV.
+pub fn hidden_power(ivs: &HashMap<String, u16>, generation: &Generation) -> (PokeType, u8) {
+    if *generation == Generation::GEN2 {
+        let dv = |stat: &str| ivs[stat] / 2;
+        let (atk, def, spd, spc) = (dv("atk"), dv("def"), dv("spd"), dv("spatk"));
+
+        let hp_type = HIDDEN_POWER_TYPES[(4 * (atk % 4) + def % 4) as usize];
+        let msb_sum = (spc >> 3) + 2 * (spd >> 3) + 4 * (def >> 3) + 8 * (atk >> 3);
+        let power = (5 * msb_sum + spc % 4) / 2 + 31;
+        return (hp_type, power as u8);
+    }
+
+    let bit_sum = |shift: u16| -> u16 {
+        BIT_ORDER
+            .iter()
+            .enumerate()
+            .map(|(i, stat)| ((ivs[*stat] >> shift) & 1) << i)
+            .sum()
+    };
+
+    let hp_type = HIDDEN_POWER_TYPES[(bit_sum(0) * 15 / 63) as usize];
+    let power = if *generation >= Generation::GEN6 {
+        60
+    } else {
+        bit_sum(1) * 40 / 63 + 30
+    };
+    (hp_type, power as u8)
+}
+
+/// Fill the unpinned IVs with the highest values that still give Hidden Power the requested type.
+///
+/// Ties on the IV total are broken by Hidden Power's base power.
+pub fn fill_hidden_power(
+    stats: &mut HashMap<String, u16>,
+    unpinned: &[&str],
+    stat_max: u16,
+    hp_type: &PokeType,
+    generation: &Generation,
+) -> Result<(), SpecErrors> {
+    if !has_hidden_power(generation) {
+        return Err(HiddenPowerGenerationError {
+            generation: generation.clone().into(),
+        });
+    }
+
+    if !HIDDEN_POWER_TYPES.contains(hp_type) {
+        return Err(HiddenPowerTypeError {
+            hp_type: hp_type.to_string(),
+        });
+    }
+
+    // Only the lowest bits of each IV matter, so each unpinned IV only needs to try the highest value for each pattern
+    // of them: its parity from Gen 3, or its DV modulo 4 in Gen 2, where only Attack and Defense decide the type.
+    // That's at most 64 spreads, and the highest IV total among them wins.
+    let candidates: Vec<Vec<u16>> = unpinned
+        .iter()
+        .map(|stat| {
+            let patterns: Vec<u16> = match (*generation == Generation::GEN2, *stat) {
+                (true, "atk" | "def") => (0..4).collect(),
+                (true, _) => return vec![stat_max],
+                (false, _) => (0..2).collect(),
+            };
+            patterns
+                .iter()
+                .filter_map(|pattern| {
+                    (0..=stat_max).rev().find(|value| match *generation == Generation::GEN2 {
+                        true => value / 2 % 4 == *pattern,
+                        false => value % 2 == *pattern,
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut spread = stats.clone();
+    let mut best: Option<((u16, u8), Vec<u16>)> = None;
+    for values in candidates.into_iter().multi_cartesian_product() {
+        for (stat, value) in unpinned.iter().zip(&values) {
+            spread.insert(stat.to_string(), *value);
+        }
+        let (spread_type, power) = hidden_power(&spread, generation);
+        let rank = (spread.values().sum::<u16>(), power);
+        if spread_type == *hp_type && best.as_ref().is_none_or(|(best_rank, _)| rank > *best_rank) {
+            best = Some((rank, values));
+        }
+    }
+    let best = best.map(|(_, values)| {
+        for (stat, value) in unpinned.iter().zip(values) {
+            spread.insert(stat.to_string(), value);
+        }
+        spread
+    });
+
+    match best {
+        Some(spread) => {
+            *stats = spread;
+            Ok(())
+        }
+        None => Err(HiddenPowerIvError {
+            hp_type: hp_type.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::STAT_NAMES;
+
+    fn fill(pinned: &[(&str, u16)], hp_type: PokeType, generation: Generation) -> Result<HashMap<String, u16>, SpecErrors> {
+        let mut stats: HashMap<String, u16> = pinned.iter().map(|(stat, value)| (stat.to_string(), *value)).collect();
+        let unpinned: Vec<&str> = STAT_NAMES.iter().copied().filter(|stat| !stats.contains_key(*stat)).collect();
+        fill_hidden_power(&mut stats, &unpinned, 31, &hp_type, &generation).map(|_| stats)
+    }
+
+    #[test]
+    fn fills_the_highest_spread_for_the_type() {
+        // The well-known Hidden Power Fire spread: 31/30/31/30/31/30 in HP/Atk/Def/SpA/SpD/Spe
+        let stats = fill(&[], PokeType::Fire, Generation::GEN7).unwrap();
+        let expected = [("hp", 31), ("atk", 30), ("def", 31), ("spatk", 30), ("spdef", 31), ("spd", 30)];
+        assert_eq!(stats, expected.iter().map(|(stat, value)| (stat.to_string(), *value)).collect());
+
+        let stats = fill(&[], PokeType::Ice, Generation::GEN2).unwrap();
+        assert_eq!(hidden_power(&stats, &Generation::GEN2).0, PokeType::Ice);
+    }
+
+    #[test]
+    fn keeps_pinned_ivs() {
+        let stats = fill(&[("atk", 0)], PokeType::Grass, Generation::GEN5).unwrap();
+        assert_eq!(stats["atk"], 0);
+        assert_eq!(hidden_power(&stats, &Generation::GEN5).0, PokeType::Grass);
+
+        let all_pinned: Vec<(&str, u16)> = STAT_NAMES.iter().map(|stat| (*stat, 31)).collect();
+        assert!(fill(&all_pinned, PokeType::Dark, Generation::GEN7).is_ok());
+        assert!(fill(&all_pinned, PokeType::Fire, Generation::GEN7).is_err());
+    }
+}
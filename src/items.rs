@@ -0,0 +1,215 @@
+use crate::api::item::PokeItem;
+use crate::enums::Generation;
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{IllegalItemError, ItemGenerationError};
+
+/// Item categories that only exist within a window of generations, regardless of what PokeAPI reports
+static CATEGORY_GENERATIONS: [(&str, Generation, Generation); 2] = [
+    ("mega-stones", Generation::GEN6, Generation::GEN7),
+    ("z-crystals", Generation::GEN7, Generation::GEN7),
+];
+
+/// Items that can only be held by (or only have an effect on) specific species.
+/// A species also matches its alternate forms, e.g. `necrozma` matches `necrozma-dawn`.
+static SPECIES_ITEMS: [(&str, &[&str]); 102] = [
+    // Mega stones and primal orbs
+    ("venusaurite", &["venusaur"]),
+    ("charizardite-x", &["charizard"]),
+    ("charizardite-y", &["charizard"]),
+    ("blastoisinite", &["blastoise"]),
+    ("beedrillite", &["beedrill"]),
+    ("pidgeotite", &["pidgeot"]),
+    ("alakazite", &["alakazam"]),
+    ("slowbronite", &["slowbro"]),
+    ("gengarite", &["gengar"]),
+    ("kangaskhanite", &["kangaskhan"]),
+    ("pinsirite", &["pinsir"]),
+    ("gyaradosite", &["gyarados"]),
+    ("aerodactylite", &["aerodactyl"]),
+    ("mewtwonite-x", &["mewtwo"]),
+    ("mewtwonite-y", &["mewtwo"]),
+    ("ampharosite", &["ampharos"]),
+    ("steelixite", &["steelix"]),
+    ("scizorite", &["scizor"]),
+    ("heracronite", &["heracross"]),
+    ("houndoominite", &["houndoom"]),
+    ("tyranitarite", &["tyranitar"]),
+    ("sceptilite", &["sceptile"]),
+    ("blazikenite", &["blaziken"]),
+    ("swampertite", &["swampert"]),
+    ("gardevoirite", &["gardevoir"]),
+    ("sablenite", &["sableye"]),
+    ("mawilite", &["mawile"]),
+    ("aggronite", &["aggron"]),
+    ("medichamite", &["medicham"]),
+    ("manectite", &["manectric"]),
+    ("sharpedonite", &["sharpedo"]),
+    ("cameruptite", &["camerupt"]),
+    ("altarianite", &["altaria"]),
+    ("banettite", &["banette"]),
+    ("absolite", &["absol"]),
+    ("glalitite", &["glalie"]),
+    ("salamencite", &["salamence"]),
+    ("metagrossite", &["metagross"]),
+    ("latiasite", &["latias"]),
+    ("latiosite", &["latios"]),
+    ("lopunnite", &["lopunny"]),
+    ("garchompite", &["garchomp"]),
+    ("lucarionite", &["lucario"]),
+    ("abomasite", &["abomasnow"]),
+    ("galladite", &["gallade"]),
+    ("audinite", &["audino"]),
+    ("diancite", &["diancie"]),
+    ("red-orb", &["groudon"]),
+    ("blue-orb", &["kyogre"]),
+    // Species-exclusive Z-crystals
+    ("pikanium-z", &["pikachu"]),
+    ("pikashunium-z", &["pikachu"]),
+    ("aloraichium-z", &["raichu-alola"]),
+    ("decidium-z", &["decidueye"]),
+    ("incinium-z", &["incineroar"]),
+    ("primarium-z", &["primarina"]),
+    ("eevium-z", &["eevee"]),
+    ("snorlium-z", &["snorlax"]),
+    ("mewnium-z", &["mew"]),
+    ("marshadium-z", &["marshadow"]),
+    ("tapunium-z", &["tapu-koko", "tapu-lele", "tapu-bulu", "tapu-fini"]),
+    ("lycanium-z", &["lycanroc"]),
+    ("mimikium-z", &["mimikyu"]),
+    ("kommonium-z", &["kommo-o"]),
+    ("solganium-z", &["solgaleo", "necrozma"]),
+    ("lunalium-z", &["lunala", "necrozma"]),
+    ("ultranecrozium-z", &["necrozma"]),
+    // Items that trigger a form change
+    ("rusted-sword", &["zacian"]),
+    ("rusted-shield", &["zamazenta"]),
+    ("griseous-orb", &["giratina"]),
+    ("douse-drive", &["genesect"]),
+    ("shock-drive", &["genesect"]),
+    ("burn-drive", &["genesect"]),
+    ("chill-drive", &["genesect"]),
+    ("bug-memory", &["silvally"]),
+    ("dark-memory", &["silvally"]),
+    ("dragon-memory", &["silvally"]),
+    ("electric-memory", &["silvally"]),
+    ("fairy-memory", &["silvally"]),
+    ("fighting-memory", &["silvally"]),
+    ("fire-memory", &["silvally"]),
+    ("flying-memory", &["silvally"]),
+    ("ghost-memory", &["silvally"]),
+    ("grass-memory", &["silvally"]),
+    ("ground-memory", &["silvally"]),
+    ("ice-memory", &["silvally"]),
+    ("poison-memory", &["silvally"]),
+    ("psychic-memory", &["silvally"]),
+    ("rock-memory", &["silvally"]),
+    ("steel-memory", &["silvally"]),
+    ("water-memory", &["silvally"]),
+    // Items that only boost specific species
+    ("light-ball", &["pikachu", "raichu"]),
+    ("thick-club", &["cubone", "marowak"]),
+    ("leek", &["farfetchd", "sirfetchd"]),
+    ("stick", &["farfetchd", "sirfetchd"]), // The Leek's name before Gen 8
+    ("lucky-punch", &["chansey"]),
+    ("metal-powder", &["ditto"]),
+    ("quick-powder", &["ditto"]),
+    ("deep-sea-tooth", &["clamperl"]),
+    ("deep-sea-scale", &["clamperl"]),
+    ("soul-dew", &["latias", "latios"]),
+    ("adamant-orb", &["dialga"]),
+    ("lustrous-orb", &["palkia"]),
+];
+
+/// Holders in `SPECIES_ITEMS` that only have a use for an item from a generation on
+static HOLDERS_SINCE: [(&str, &str, Generation); 1] = [
+    // Raichu holds a Light Ball to breed a Pichu that knows Volt Tackle, which only exists from Gen 4
+    ("light-ball", "raichu", Generation::GEN4),
+];
+
+/// PokeAPI lists Z-crystals twice, once as a held item and once as a key item. Strip the suffix to get the crystal.
+fn base_item_name(item: &str) -> &str {
+    item.trim_end_matches("--held").trim_end_matches("--bag")
+}
+
+fn is_species_or_form(species: &str, base_species: &str) -> bool {
+    species == base_species || species.starts_with(format!("{base_species}-").as_str())
+}
+
+/// Check that a holder listed for an item has a use for it in the target generation. See `HOLDERS_SINCE`.
+fn is_holder_in_generation(item: &str, holder: &str, generation: Option<&Generation>) -> bool {
+    HOLDERS_SINCE
+        .iter()
+        .filter(|(since_item, since_holder, _)| *since_item == item && *since_holder == holder)
+        .all(|(_, _, since)| generation.is_none_or(|generation| generation >= since))
+}
+
+/// Check that a species may hold a given item in the target generation.
+///
+/// If no generation was given, the item is assumed to be used in a generation where it exists.
+pub fn check_item(species: &str, item: &PokeItem, generation: Option<&Generation>) -> Vec<SpecErrors> {
+    let mut errors: Vec<SpecErrors> = Vec::new();
+    let item_name = base_item_name(&item.name);
+    let species = species.to_lowercase();
+
+    if let Some(generation) = generation {
+        // PokeAPI's game indices are incomplete for recent items, so only trust them when there are some
+        let mut exists = item.generations.is_empty() || item.generations.contains(generation);
+        for (category, first, last) in &CATEGORY_GENERATIONS {
+            if item.category == *category {
+                exists &= (first..=last).contains(&generation);
+            }
+        }
+
+        if !exists {
+            errors.push(ItemGenerationError {
+                item: item_name.to_string(),
+                generation: generation.clone().into(),
+            });
+        }
+    }
+
+    for (species_item, holders) in &SPECIES_ITEMS {
+        if *species_item == item_name
+            && !holders.iter().any(|holder| {
+                is_species_or_form(&species, holder) && is_holder_in_generation(item_name, holder, generation)
+            })
+        {
+            errors.push(IllegalItemError {
+                species: species.clone(),
+                item: item_name.to_string(),
+            });
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn item(name: &str) -> PokeItem {
+        PokeItem {
+            name: name.to_string(),
+            category: String::from("species-specific"),
+            generations: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn species_items_need_their_species() {
+        assert!(check_item("silvally-fire", &item("fire-memory"), None).is_empty());
+        assert_eq!(check_item("arceus", &item("fire-memory"), None).len(), 1);
+        assert!(check_item("marowak-alola", &item("thick-club"), Some(&Generation::GEN7)).is_empty());
+        assert_eq!(check_item("pidgey", &item("stick"), Some(&Generation::GEN3)).len(), 1);
+    }
+
+    #[test]
+    fn holders_only_count_from_their_generation() {
+        assert!(check_item("pikachu", &item("light-ball"), Some(&Generation::GEN2)).is_empty());
+        assert_eq!(check_item("raichu", &item("light-ball"), Some(&Generation::GEN3)).len(), 1);
+        assert!(check_item("raichu", &item("light-ball"), Some(&Generation::GEN4)).is_empty());
+        assert!(check_item("raichu", &item("light-ball"), None).is_empty());
+    }
+}
@@ -1,19 +1,36 @@
 #[macro_use]
 extern crate num_derive;
+mod abilities;
 mod api;
+mod balls;
+mod breeding;
+mod bundle;
 mod cache;
 pub mod command_logic;
+mod config;
 mod console;
 pub mod enums;
 mod errors;
+mod events;
+mod evolution;
+mod hidden_power;
+mod items;
+mod mechanics;
+mod moveset;
+mod names;
+mod output;
+mod prefetch;
 pub mod spec;
+mod spread;
+mod stat_calc;
 mod util;
 
 use crate::command_logic::CommandLogic;
 use clap::Subcommand;
-use clap::{Args, Parser};
+use clap::{ArgGroup, Args, Parser};
 use figment::providers::Format;
 use miette::{IntoDiagnostic, Result};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "PokeSpecRS")]
@@ -28,43 +45,20 @@ struct Cli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     // The main command. Used to generate Pokemon specs.
-    Generate {
-        // Required, positional
+    Generate(Box<GenerateArgs>),
+
+    // Work out the possible IVs of a pokemon from the stats shown on its summary screen.
+    // Each --level is paired with the --stats given in the same position.
+    Ivcalc {
         species: String,
 
-        // Optional, flag-based (with --)
-        #[arg(short, long)]
-        ability: Option<String>,
-        #[arg(short, long, default_value_t = 1)]
-        level: u8,
-        #[arg(long, alias = "nick")]
-        nickname: Option<String>,
-        #[arg(short, long, default_value_t = false)]
-        shiny: bool,
-        #[arg(long, default_value = "PokeSpecRS")]
-        ot: String,
-        #[arg(long)]
-        tid: Option<usize>,
-        #[arg(long)]
-        sid: Option<usize>,
-        #[arg(short, long)]
-        gender: Option<String>,
-        #[arg(short, long, alias="ba", default_value = "Poke-ball")]
-        ball: String,
+        #[arg(short, long, required = true)]
+        level: Vec<u8>,
         #[arg(short, long, alias = "nat")]
-        nature: Option<String>,
-        #[arg(long)]
-        ivatk: Option<u16>,
-        #[arg(long)]
-        ivspatk: Option<u16>,
-        #[arg(long)]
-        ivdef: Option<u16>,
-        #[arg(long)]
-        ivspdef: Option<u16>,
-        #[arg(long)]
-        ivspd: Option<u16>,
-        #[arg(long)]
-        ivhp: Option<u16>,
+        nature: String,
+        // Observed stats, in summary screen order: hp,atk,def,spatk,spdef,spd
+        #[arg(long, required = true)]
+        stats: Vec<String>,
         #[arg(long)]
         evatk: Option<u16>,
         #[arg(long)]
@@ -77,8 +71,58 @@ pub enum Commands {
         evspd: Option<u16>,
         #[arg(long)]
         evhp: Option<u16>,
-        #[arg(long, num_args = 1..4)]
-        moveset: Vec<String>,
+    },
+
+    // Show the basics of a species, e.g. its types, base stats, abilities, and evolutions
+    Info {
+        species: String,
+
+        #[arg(short, long)]
+        form: Option<String>,
+        // text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    // List every move a species can learn, along with how, at what level, and in which generations
+    Learnset {
+        species: String,
+
+        #[arg(short, long)]
+        form: Option<String>,
+        // Only show moves learnable in this generation
+        #[arg(long = "gen")]
+        generation: Option<u8>,
+        // Only show moves learned this way, e.g. level-up, egg, machine, or tutor
+        #[arg(short, long)]
+        method: Option<String>,
+        // Leave out moves learned by leveling up past this level
+        #[arg(long)]
+        max_level: Option<u8>,
+        // text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    // Find every cached pokemon that can legally have a combination of moves and an ability
+    Search {
+        #[arg(short, long = "move")]
+        moves: Vec<String>,
+        #[arg(short, long)]
+        ability: Option<String>,
+        #[arg(short, long, default_value_t = 100)]
+        level: u8,
+        #[arg(long = "gen")]
+        generation: Option<u8>,
+        // text or json
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    // Show the details of a move, e.g. its type, power, and accuracy, and how they changed between generations
+    Move {
+        name: String,
+        // Show the move as it was in this generation
         #[arg(long = "gen")]
         generation: Option<u8>,
     },
@@ -88,6 +132,97 @@ pub enum Commands {
     Cache(CacheArgs),
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct GenerateArgs {
+    // Required, positional
+    species: String,
+
+    // Optional, flag-based (with --)
+    #[arg(short, long)]
+    form: Option<String>,
+    #[arg(short, long)]
+    ability: Option<String>,
+    #[arg(short, long, default_value_t = 1)]
+    level: u8,
+    #[arg(long, alias = "nick")]
+    nickname: Option<String>,
+    #[arg(short, long, default_value_t = false)]
+    shiny: bool,
+    #[arg(long, default_value = "PokeSpecRS")]
+    ot: String,
+    #[arg(long)]
+    tid: Option<usize>,
+    #[arg(long)]
+    sid: Option<usize>,
+    #[arg(short, long)]
+    gender: Option<String>,
+    #[arg(short, long, alias="ba", default_value = "poke-ball")]
+    ball: String,
+    #[arg(short, long)]
+    item: Option<String>,
+    #[arg(short, long, alias = "nat")]
+    nature: Option<String>,
+    #[arg(long)]
+    ivatk: Option<u16>,
+    #[arg(long)]
+    ivspatk: Option<u16>,
+    #[arg(long)]
+    ivdef: Option<u16>,
+    #[arg(long)]
+    ivspdef: Option<u16>,
+    #[arg(long)]
+    ivspd: Option<u16>,
+    #[arg(long)]
+    ivhp: Option<u16>,
+    #[arg(long)]
+    evatk: Option<u16>,
+    #[arg(long)]
+    evspatk: Option<u16>,
+    #[arg(long)]
+    evdef: Option<u16>,
+    #[arg(long)]
+    evspdef: Option<u16>,
+    #[arg(long)]
+    evspd: Option<u16>,
+    #[arg(long)]
+    evhp: Option<u16>,
+    // How to fill unpinned IVs: random, zero, or perfect-N. --hidden-power picks the IVs itself, so they can't be combined.
+    #[arg(long, default_value = "random", conflicts_with = "hidden_power")]
+    iv_strategy: String,
+    // How to fill unpinned EVs: random, zero, or competitive
+    #[arg(long, default_value = "random")]
+    ev_strategy: String,
+    // Up to four moves, in slot order
+    #[arg(long, num_args = 1..)]
+    moveset: Vec<String>,
+    // How to fill the moveset if none was given: level-up, random, stab, or competitive
+    #[arg(long, default_value = "level-up")]
+    moveset_strategy: String,
+    // PP Ups applied to each move (0-3), in slot order
+    #[arg(long, num_args = 1..)]
+    pp_ups: Vec<u8>,
+    // Constrain the IVs so that Hidden Power has this type
+    #[arg(long)]
+    hidden_power: Option<String>,
+    // Gen 9 only
+    #[arg(long)]
+    tera_type: Option<String>,
+    // Gen 8 only
+    #[arg(long)]
+    dynamax_level: Option<u8>,
+    // Gen 8 only
+    #[arg(long, alias = "gmax", default_value_t = false)]
+    gigantamax: bool,
+    #[arg(long = "gen")]
+    generation: Option<u8>,
+    // The game the spec must be legal in, as a PokeAPI version group, e.g. sword-shield. Implies its generation.
+    #[arg(long)]
+    game: Option<String>,
+    // Fetch the species, moves, and item again first if they have been cached for longer than the TTL
+    #[arg(long, default_value_t = false)]
+    refresh: bool,
+}
+
 #[derive(Debug, Args, Clone)]
 #[command(args_conflicts_with_subcommands = true)]
 struct CacheArgs {
@@ -102,15 +237,42 @@ enum CacheCommands {
     Check { species: String },
     Purge { species: String },
     Validate {},
+    // Fetch a pokemon again, or every stale pokemon, move, and item if none is given
+    Refresh { species: Option<String> },
+    // Derive a pokemon, or every cached pokemon, move, and item, again from the stored PokeAPI responses, without the network
+    Rebuild { species: Option<String> },
+    // Write the cache to a compressed bundle, e.g. to copy it to a machine without internet access
+    Export { file: PathBuf },
+    // Replace the cache with one from a bundle made by `cache export`
+    Import { file: PathBuf },
+    // Fill the cache ahead of time, e.g. for machines that will be used offline
+    #[command(group(ArgGroup::new("source").required(true).args(["all", "generation", "species_list"])))]
+    Prefetch {
+        // Every pokemon, including alternate forms
+        #[arg(long)]
+        all: bool,
+        // Every species introduced in this generation
+        #[arg(long = "gen")]
+        generation: Option<u8>,
+        // A file with one species or form per line
+        #[arg(long)]
+        species_list: Option<PathBuf>,
+        // How many pokemon to fetch from PokeAPI at once
+        #[arg(short, long, default_value_t = 4)]
+        jobs: usize,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Cli::parse();
 
-    println!("{:?}", args.command);
-
     match &args.command {
-        Commands::Generate { .. } => command_logic::Generate.execute(args.command),
+        Commands::Generate(..) => command_logic::Generate.execute(args.command),
+        Commands::Ivcalc { .. } => command_logic::IvCalc.execute(args.command),
+        Commands::Info { .. } => command_logic::Info.execute(args.command),
+        Commands::Learnset { .. } => command_logic::Learnset.execute(args.command),
+        Commands::Search { .. } => command_logic::Search.execute(args.command),
+        Commands::Move { .. } => command_logic::MoveInfo.execute(args.command),
         Commands::Cache { .. } => command_logic::Cache.execute(args.command),
     }
 }
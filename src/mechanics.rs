@@ -0,0 +1,104 @@
+use crate::enums::{Generation, TeraType};
+use crate::names::display_name;
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{DynamaxLevelError, GigantamaxError, MechanicGenerationError};
+
+const MAX_DYNAMAX_LEVEL: u8 = 10;
+
+/// Every pokemon with a Gigantamax form. Only these exact forms qualify, e.g. Galarian Meowth and Pikachu in a cap can't
+/// Gigantamax, while both of Toxtricity's and Urshifu's forms can.
+static GIGANTAMAX_POKEMON: [&str; 34] = [
+    "venusaur", "charizard", "blastoise", "butterfree", "pikachu", "meowth", "machamp", "gengar",
+    "kingler", "lapras", "eevee", "snorlax", "garbodor", "melmetal", "rillaboom", "cinderace",
+    "inteleon", "corviknight", "orbeetle", "drednaw", "coalossal", "flapple", "appletun",
+    "sandaconda", "toxtricity-amped", "toxtricity-low-key", "centiskorch", "hatterene", "grimmsnarl", "alcremie",
+    "copperajah", "duraludon", "urshifu-single-strike", "urshifu-rapid-strike",
+];
+
+/// The only games with Dynamax and Gigantamax. Gen 8 also includes Let's Go, Brilliant Diamond and Shining Pearl, and
+/// Legends: Arceus, which have neither.
+static DYNAMAX_GAMES: [&str; 1] = ["sword-shield"];
+
+/// Check that the generation-specific battle mechanics of a spec are legal.
+///
+/// Terastallization only exists in Gen 9, while Dynamax and Gigantamax only exist in Sword and Shield.
+/// If no generation was given, the spec may use either, but not both. If a game was given, Dynamax is checked against it.
+pub fn check_mechanics(
+    species: &str,
+    generation: Option<&Generation>,
+    game: Option<&str>,
+    tera_type: Option<&TeraType>,
+    dynamax_level: Option<u8>,
+    gigantamax: bool,
+) -> Vec<SpecErrors> {
+    let mut errors: Vec<SpecErrors> = Vec::new();
+    let uses_dynamax = dynamax_level.is_some() || gigantamax;
+
+    let dynamax_generation = match (generation, tera_type) {
+        (Some(generation), _) => Some(generation.clone()),
+        (None, Some(_)) => Some(Generation::GEN9),
+        (None, None) => None,
+    };
+
+    if tera_type.is_some() && generation.is_some_and(|generation| *generation != Generation::GEN9) {
+        errors.push(MechanicGenerationError {
+            mechanic: "Terastallization".to_string(),
+            generation: Into::<String>::into(generation.unwrap().clone()),
+        });
+    }
+
+    if uses_dynamax && dynamax_generation.as_ref().is_some_and(|generation| *generation != Generation::GEN8) {
+        errors.push(MechanicGenerationError {
+            mechanic: "Dynamax".to_string(),
+            generation: Into::<String>::into(dynamax_generation.unwrap()),
+        });
+    } else if uses_dynamax && let Some(game) = game && !DYNAMAX_GAMES.contains(&game) {
+        errors.push(MechanicGenerationError {
+            mechanic: "Dynamax".to_string(),
+            generation: display_name(game),
+        });
+    }
+
+    if let Some(level) = dynamax_level
+        && level > MAX_DYNAMAX_LEVEL
+    {
+        errors.push(DynamaxLevelError {
+            level: level.to_string(),
+        });
+    }
+
+    let pokemon = species.to_lowercase();
+    let pokemon = pokemon.trim_end_matches("-gmax");
+    if gigantamax && !GIGANTAMAX_POKEMON.contains(&pokemon) {
+        errors.push(GigantamaxError {
+            species: species.to_string(),
+        });
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_gigantamax_forms_can_gigantamax() {
+        for pokemon in ["meowth", "meowth-gmax", "toxtricity-low-key", "urshifu-rapid-strike-gmax"] {
+            assert!(check_mechanics(pokemon, None, None, None, None, true).is_empty(), "{pokemon}");
+        }
+        for pokemon in ["meowth-alola", "meowth-galar", "pikachu-original-cap", "mewtwo"] {
+            assert_eq!(check_mechanics(pokemon, None, None, None, None, true).len(), 1, "{pokemon}");
+        }
+    }
+
+    #[test]
+    fn dynamax_is_only_in_sword_and_shield() {
+        let gen8 = Some(&Generation::GEN8);
+        assert!(check_mechanics("charizard", gen8, Some("sword-shield"), None, Some(10), true).is_empty());
+        assert!(check_mechanics("charizard", gen8, None, None, Some(10), false).is_empty());
+        assert_eq!(check_mechanics("charizard", gen8, Some("legends-arceus"), None, Some(10), false).len(), 1);
+        assert_eq!(check_mechanics("charizard", gen8, Some("lets-go-pikachu-lets-go-eevee"), None, None, true).len(), 1);
+        assert_eq!(check_mechanics("charizard", Some(&Generation::GEN9), None, None, Some(10), false).len(), 1);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::api::move_data::PokeMoveData;
+use crate::api::pokemon_move::PokeMove;
+use crate::cache::{fetch_learnset, fetch_move_data, fetch_types, get_and_cache_move_data};
+use crate::enums::{DamageClass, Generation, LearnMethod, PokeType};
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{PpUpBasePpError, PpUpCountError, PpUpValueError};
+use miette::{miette, Result};
+use rand::{rng, Rng};
+use rand::seq::{IndexedRandom, SliceRandom};
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// A pokemon can know at most four moves at once
+pub const MAX_MOVES: usize = 4;
+
+/// A move's PP can be raised by a PP Up at most three times
+pub const MAX_PP_UPS: u8 = 3;
+
+/// How much more likely STAB moves are to be picked than any other move
+const STAB_WEIGHT: f64 = 3.0;
+
+/// How many uncached moves a competitive moveset fetches from PokeAPI at most. Cached moves are always considered.
+const MAX_MOVE_FETCHES: usize = 16;
+
+/// A move in a pokemon's moveset, as it was in the spec's generation, along with how many PP Ups it has had
+#[derive(Clone, Debug)]
+pub struct SpecMove {
+    pub data: PokeMoveData,
+    pub pp_ups: u8,
+}
+
+impl SpecMove {
+    /// The move's PP once its PP Ups are applied. Each PP Up adds a fifth of the base PP, rounded down.
+    pub fn max_pp(&self) -> Option<u8> {
+        self.data
+            .pp
+            .map(|pp| (pp as u16 + pp as u16 * self.pp_ups as u16 / 5) as u8)
+    }
+}
+
+/// Check that the PP Ups given for each move, in slot order, can be applied in the target generation.
+/// If no generation was given, the latest rules apply.
+pub fn check_pp_ups(moves: &[PokeMoveData], pp_ups: &[u8], generation: Option<&Generation>) -> Vec<SpecErrors> {
+    let mut errors: Vec<SpecErrors> = Vec::new();
+
+    if pp_ups.len() > moves.len() {
+        errors.push(PpUpCountError {
+            count: pp_ups.len().to_string(),
+            moves: moves.len().to_string(),
+        });
+    }
+
+    for (move_data, count) in moves.iter().zip(pp_ups) {
+        if *count > MAX_PP_UPS {
+            errors.push(PpUpValueError {
+                pk_move: move_data.name.clone(),
+                value: count.to_string(),
+            });
+        }
+
+        // Only Sketch has 1 base PP, and the games refuse to use PP Ups on it from Gen 3
+        if *count > 0 && move_data.pp == Some(1) && generation.is_none_or(|generation| *generation >= Generation::GEN3) {
+            errors.push(PpUpBasePpError {
+                pk_move: move_data.name.clone(),
+                generation: generation.map_or(String::from("the latest generation"), |generation| generation.clone().into()),
+            });
+        }
+    }
+
+    errors
+}
+
+/// How a moveset gets filled in when no moves were given
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MovesetStrategy {
+    /// The last four moves learned by leveling up, as a wild or gift pokemon of that level knows in the games
+    LevelUp,
+    /// Any four moves that can be learned by leveling up, from a machine, or from a tutor
+    Random,
+    /// Like `Random`, but moves that share a type with the pokemon are more likely
+    Stab,
+    /// Damaging moves of as many different types as possible, preferring STAB, topped up with status moves
+    Competitive,
+}
+
+/// A basic string-to-enum conversion. Accepts `level-up`, `random`, `stab`, and `competitive`.
+impl TryFrom<&str> for MovesetStrategy {
+    type Error = miette::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "level-up" | "levelup" => Ok(MovesetStrategy::LevelUp),
+            "random" => Ok(MovesetStrategy::Random),
+            "stab" => Ok(MovesetStrategy::Stab),
+            "competitive" => Ok(MovesetStrategy::Competitive),
+            _ => Err(miette!("'{}' is not a valid moveset strategy!", value)),
+        }
+    }
+}
+
+impl Display for MovesetStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MovesetStrategy::LevelUp => write!(f, "level-up"),
+            MovesetStrategy::Random => write!(f, "random"),
+            MovesetStrategy::Stab => write!(f, "stab"),
+            MovesetStrategy::Competitive => write!(f, "competitive"),
+        }
+    }
+}
+
+/// The most recent generation a learnset has any moves in
+fn latest_generation(learnset: &[PokeMove]) -> Option<Generation> {
+    learnset
+        .iter()
+        .flat_map(|pk_move| pk_move.generations.iter().map(|method| method.generation.clone()))
+        .max()
+}
+
+/// The last four moves learned by leveling up at or below a level, in the order they were learned
+fn last_level_up_moves(learnset: &[PokeMove], level: u8, generation: &Generation) -> Vec<String> {
+    // A move learned at several levels is remembered from the latest one
+    let mut learned_at: HashMap<&str, u8> = HashMap::new();
+    for pk_move in learnset {
+        for method in &pk_move.generations {
+            let method_level = method.level_learned_at.unwrap_or(0);
+            if method.method == LearnMethod::LevelUp && method.generation == *generation && method_level <= level {
+                let latest = learned_at.entry(&pk_move.name).or_insert(method_level);
+                *latest = (*latest).max(method_level);
+            }
+        }
+    }
+
+    let mut level_up_moves: Vec<(u8, &str)> = learned_at.into_iter().map(|(name, level)| (level, name)).collect();
+    level_up_moves.sort();
+    level_up_moves
+        .iter()
+        .rev()
+        .take(MAX_MOVES)
+        .rev()
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+/// Every move that can be learned at or below a level without breeding or events
+fn learnable_moves(learnset: &[PokeMove], level: u8, generation: &Generation) -> Vec<String> {
+    learnset
+        .iter()
+        .filter(|pk_move| {
+            pk_move.generations.iter().any(|method| {
+                method.generation == *generation
+                    && match method.method {
+                        LearnMethod::LevelUp => method.level_learned_at.unwrap_or(0) <= level,
+                        LearnMethod::Machine | LearnMethod::Tutor => true,
+                        _ => false,
+                    }
+            })
+        })
+        .map(|pk_move| pk_move.name.clone())
+        .collect()
+}
+
+/// Pick a random moveset where STAB moves are more likely than others.
+///
+/// Moves are drawn at random and kept with a chance in proportion to their weight, so only the details of drawn moves
+/// are needed, rather than those of every move the pokemon can learn.
+fn stab_moves(conn: &Connection, moves: &[String], types: &[PokeType]) -> Result<Vec<String>> {
+    let mut rng = rng();
+    let mut remaining = moves.to_vec();
+    let mut is_stab: HashMap<String, bool> = HashMap::new();
+    let mut moveset: Vec<String> = Vec::new();
+    while moveset.len() < MAX_MOVES && !remaining.is_empty() {
+        let index = rng.random_range(0..remaining.len());
+        let pk_move = &remaining[index];
+        let stab = match is_stab.get(pk_move) {
+            Some(stab) => *stab,
+            None => {
+                let stab = get_and_cache_move_data(conn, pk_move)?.is_some_and(|move_data| {
+                    move_data.damage_class != Some(DamageClass::Status)
+                        && move_data.move_type.is_some_and(|move_type| types.contains(&move_type))
+                });
+                is_stab.insert(pk_move.clone(), stab);
+                stab
+            }
+        };
+        if stab || rng.random_bool(1.0 / STAB_WEIGHT) {
+            moveset.push(remaining.swap_remove(index));
+        }
+    }
+
+    Ok(moveset)
+}
+
+/// Pick damaging moves that each have a different type, STAB first, then round the moveset out with status moves.
+/// Moves are considered in a random order, and only until the moveset can't change.
+fn competitive_moves(conn: &Connection, moves: &[String], types: &[PokeType]) -> Result<Vec<String>> {
+    let mut moves = moves.to_vec();
+    moves.shuffle(&mut rng());
+
+    let mut attacks: Vec<(bool, String, Option<PokeType>)> = Vec::new();
+    let mut status_moves: Vec<String> = Vec::new();
+    let (mut stab_types, mut other_types): (HashSet<PokeType>, HashSet<PokeType>) = (HashSet::new(), HashSet::new());
+    let mut fetches = 0;
+    for pk_move in moves {
+        // Once there's a STAB attack of each of the pokemon's types and enough other types to fill the moveset,
+        // no later move would be picked
+        if stab_types.len() == types.len() && stab_types.len() + other_types.len() >= MAX_MOVES {
+            break;
+        }
+
+        let move_data = match fetch_move_data(conn, &pk_move)? {
+            Some(move_data) => move_data,
+            None if fetches < MAX_MOVE_FETCHES => {
+                fetches += 1;
+                match get_and_cache_move_data(conn, &pk_move)? {
+                    Some(move_data) => move_data,
+                    None => continue,
+                }
+            }
+            None => continue,
+        };
+        match move_data.damage_class {
+            Some(DamageClass::Status) => status_moves.push(pk_move),
+            Some(_) => {
+                let is_stab = move_data.move_type.is_some_and(|move_type| types.contains(&move_type));
+                if let Some(move_type) = move_data.move_type {
+                    if is_stab { &mut stab_types } else { &mut other_types }.insert(move_type);
+                }
+                attacks.push((is_stab, pk_move, move_data.move_type));
+            }
+            None => {}
+        }
+    }
+    // The sort is stable, so attacks are still shuffled within STAB and non-STAB
+    attacks.sort_by_key(|(is_stab, _, _)| !is_stab);
+
+    let mut moveset: Vec<String> = Vec::new();
+    let mut covered: HashSet<PokeType> = HashSet::new();
+    for (_, pk_move, move_type) in attacks {
+        if moveset.len() == MAX_MOVES {
+            break;
+        }
+        if move_type.is_none_or(|move_type| covered.insert(move_type)) {
+            moveset.push(pk_move);
+        }
+    }
+    moveset.extend(status_moves.into_iter().take(MAX_MOVES - moveset.len()));
+
+    Ok(moveset)
+}
+
+/// Generate a legal moveset for a cached pokemon from its learnset.
+///
+/// If no generation was given, the latest generation the pokemon has a learnset in is used.
+/// Egg and event moves are never picked, as they need a specific breeding chain or distribution.
+pub fn generate_moveset(
+    conn: &Connection,
+    species_id: i32,
+    level: u8,
+    generation: Option<&Generation>,
+    strategy: MovesetStrategy,
+) -> Result<Vec<String>> {
+    let learnset = fetch_learnset(conn, species_id)?;
+    let generation = match generation {
+        Some(generation) => generation.clone(),
+        None => match latest_generation(&learnset) {
+            Some(generation) => generation,
+            None => return Ok(Vec::new()),
+        },
+    };
+
+    match strategy {
+        MovesetStrategy::LevelUp => Ok(last_level_up_moves(&learnset, level, &generation)),
+        MovesetStrategy::Random => {
+            let moves = learnable_moves(&learnset, level, &generation);
+            Ok(moves.choose_multiple(&mut rng(), MAX_MOVES).cloned().collect())
+        }
+        MovesetStrategy::Stab => {
+            stab_moves(conn, &learnable_moves(&learnset, level, &generation), &fetch_types(conn, species_id)?)
+        }
+        MovesetStrategy::Competitive => competitive_moves(
+            conn,
+            &learnable_moves(&learnset, level, &generation),
+            &fetch_types(conn, species_id)?,
+        ),
+    }
+}
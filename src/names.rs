@@ -0,0 +1,107 @@
+use inflector::Inflector;
+
+/// Names whose canonical display form can't be recovered by title-casing their slug
+static DISPLAY_NAMES: [(&str, &str); 23] = [
+    ("poke-ball", "Poké Ball"),
+    ("flabebe", "Flabébé"),
+    ("nidoran-f", "Nidoran♀"),
+    ("nidoran-m", "Nidoran♂"),
+    ("mr-mime", "Mr. Mime"),
+    ("mr-rime", "Mr. Rime"),
+    ("mime-jr", "Mime Jr."),
+    ("farfetchd", "Farfetch'd"),
+    ("sirfetchd", "Sirfetch'd"),
+    ("type-null", "Type: Null"),
+    ("ho-oh", "Ho-Oh"),
+    ("porygon-z", "Porygon-Z"),
+    ("jangmo-o", "Jangmo-o"),
+    ("hakamo-o", "Hakamo-o"),
+    ("kommo-o", "Kommo-o"),
+    ("u-turn", "U-turn"),
+    ("v-create", "V-create"),
+    ("x-scissor", "X-Scissor"),
+    ("will-o-wisp", "Will-O-Wisp"),
+    ("double-edge", "Double-Edge"),
+    ("self-destruct", "Self-Destruct"),
+    ("kings-rock", "King's Rock"),
+    ("kings-shield", "King's Shield"),
+];
+
+/// Convert a human-friendly name into the slug PokeAPI uses for it.
+///
+/// Handles case, accents, gender symbols, punctuation, and whitespace, e.g. `Poké Ball` -> `poke-ball`,
+/// `King's Rock` -> `kings-rock`, `Mr. Mime` -> `mr-mime`, and `Nidoran♀` -> `nidoran-f`.
+/// Works on moves, abilities, items, species, and forms. Balls should use `to_ball_slug` instead.
+pub fn to_slug(name: &str) -> String {
+    let mut slug = String::new();
+
+    for c in name.trim().to_lowercase().chars() {
+        match c {
+            'é' | 'è' | 'ê' | 'ë' => slug.push('e'),
+            'á' | 'à' | 'â' | 'ä' => slug.push('a'),
+            'í' | 'ì' | 'î' | 'ï' => slug.push('i'),
+            'ó' | 'ò' | 'ô' | 'ö' => slug.push('o'),
+            'ú' | 'ù' | 'û' | 'ü' => slug.push('u'),
+            '♀' | '♂' => {
+                if !slug.ends_with('-') {
+                    slug.push('-')
+                }
+                slug.push(if c == '♀' { 'f' } else { 'm' });
+            }
+            '\'' | '’' | '.' | ':' => {}
+            ' ' | '_' | '-' => {
+                if !slug.ends_with('-') {
+                    slug.push('-')
+                }
+            }
+            _ => slug.push(c),
+        }
+    }
+
+    slug.trim_matches('-').to_string()
+}
+
+/// Convert a human-friendly ball name into its PokeAPI slug, accepting the name with or without "ball".
+/// E.g. `Great Ball`, `great`, `Pokéball`, and `Poké Ball` become `great-ball`, `great-ball`, `poke-ball`, and `poke-ball`.
+pub fn to_ball_slug(name: &str) -> String {
+    let slug = to_slug(name);
+
+    match slug.strip_suffix("ball") {
+        Some(prefix) => format!("{}-ball", prefix.trim_end_matches('-')),
+        None => format!("{slug}-ball"),
+    }
+}
+
+/// Render a PokeAPI slug as its canonical, human-friendly display name
+pub fn display_name(slug: &str) -> String {
+    match DISPLAY_NAMES.iter().find(|(name, _)| *name == slug) {
+        Some((_, display)) => display.to_string(),
+        None => slug.to_title_case(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_match_pokeapi() {
+        assert_eq!(to_slug("Nidoran♀"), "nidoran-f");
+        assert_eq!(to_slug("Nidoran ♀"), "nidoran-f");
+        assert_eq!(to_slug("Nidoran♂"), "nidoran-m");
+        assert_eq!(to_slug("Nidoran ♂"), "nidoran-m");
+        assert_eq!(to_slug("Mr. Mime"), "mr-mime");
+        assert_eq!(to_slug("Farfetch'd"), "farfetchd");
+        assert_eq!(to_slug("Farfetch’d"), "farfetchd");
+        assert_eq!(to_slug("Flabébé"), "flabebe");
+        assert_eq!(to_slug("Type: Null"), "type-null");
+    }
+
+    #[test]
+    fn display_names_round_trip() {
+        for (slug, display) in DISPLAY_NAMES {
+            assert_eq!(to_slug(display), slug);
+            assert_eq!(display_name(slug), display);
+        }
+    }
+}
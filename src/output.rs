@@ -0,0 +1,65 @@
+use itertools::Itertools;
+use miette::miette;
+use std::fmt::{Display, Formatter};
+
+/// How a command prints its results. `json` is meant for scripts, so nothing else is printed to stdout alongside it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// A basic string-to-enum conversion. Accepts `text` and `json`.
+impl TryFrom<&str> for OutputFormat {
+    type Error = miette::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(miette!("'{}' is not a valid output format!", value)),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Print a JSON document on its own, without any colouring
+pub fn print_json(value: &serde_json::Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_default())
+}
+
+/// Lay rows out in columns under a header, padding each column to its widest cell
+pub fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut lines: Vec<String> = vec![
+        render_row(headers.to_vec()),
+        widths.iter().map(|width| "-".repeat(*width)).join("  "),
+    ];
+    for row in rows {
+        lines.push(render_row(row.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,143 @@
+use crate::api::pokemon::PokemonData;
+use crate::api::{api_get_generation_species, api_get_pokemon_data, api_get_pokemon_listing, FetchMode, Fetcher};
+use crate::cache::{
+    cache_pokemon_data, fetch_species_forms, get_db_connection, is_egg_group_cached, is_evolution_chain_cached, is_species_cached,
+    response_fetcher,
+};
+use crate::enums::Generation;
+use crate::names::to_slug;
+use indicatif::{ProgressBar, ProgressStyle};
+use itertools::Itertools;
+use miette::{IntoDiagnostic, Result};
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::Mutex;
+use std::thread;
+
+/// Which pokemon to fill the cache with
+#[derive(Debug, Clone)]
+pub enum PrefetchSource {
+    All,                   // Every pokemon in PokeAPI, including alternate forms
+    Generation(Generation), // The default form of every species introduced in a generation
+    SpeciesList(PathBuf),  // A file with one species or form per line. Blank lines and lines starting with `#` are skipped.
+}
+
+/// How a prefetch went
+#[derive(Debug, Default)]
+pub struct PrefetchSummary {
+    pub fetched: usize,
+    pub skipped: usize, // Already cached, e.g. by an earlier, interrupted prefetch
+    pub missing: Vec<String>, // Names PokeAPI has no pokemon or species for
+}
+
+/// Work out the names of every pokemon to prefetch, without duplicates.
+/// Listings change as PokeAPI grows and nothing is derived from them, so their responses aren't stored.
+pub fn prefetch_names(source: &PrefetchSource) -> Result<Vec<String>> {
+    let fetcher = Fetcher::new(FetchMode::Online, |_| None);
+    let names = match source {
+        PrefetchSource::All => api_get_pokemon_listing(&fetcher)?,
+        PrefetchSource::Generation(generation) => api_get_generation_species(&fetcher, generation)?,
+        PrefetchSource::SpeciesList(path) => read_to_string(path)
+            .into_diagnostic()?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(to_slug)
+            .collect(),
+    };
+    Ok(names.into_iter().unique().collect())
+}
+
+/// Check if a pokemon, or the default form of a species by that name, has already been cached
+fn is_pokemon_cached(conn: &Connection, name: &str) -> Result<bool> {
+    Ok(is_species_cached(conn, name) || fetch_species_forms(conn, name)?.iter().any(|form| form.is_default))
+}
+
+/// Fetch and cache every given pokemon that isn't cached yet, using up to `jobs` requests to PokeAPI at once.
+///
+/// Workers only read from the cache and talk to PokeAPI, and every result is cached, along with its responses, on this
+/// thread as soon as it arrives, so an interrupted prefetch keeps everything cached so far and can be resumed by running
+/// it again.
+pub fn prefetch(conn: &Connection, names: &[String], jobs: usize) -> Result<PrefetchSummary> {
+    let mut summary = PrefetchSummary::default();
+    let mut queue: Vec<String> = Vec::new();
+    for name in names {
+        if is_pokemon_cached(conn, name)? {
+            summary.skipped += 1;
+        } else {
+            queue.push(name.clone());
+        }
+    }
+
+    let progress = ProgressBar::new(queue.len() as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{bar:40} {pos}/{len} ({eta} left) {msg}")
+            .into_diagnostic()?,
+    );
+
+    // Evolution chains and egg groups are shared, so each one is claimed by the first worker to come across it
+    let claimed_chains: Mutex<HashSet<i32>> = Mutex::new(HashSet::new());
+    let claimed_egg_groups: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+    let queue = Mutex::new(queue.into_iter());
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = channel::<(String, Result<Option<PokemonData>>)>();
+
+    let result: Result<()> = thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            let sender = sender.clone();
+            let (queue, stop) = (&queue, &stop);
+            let (claimed_chains, claimed_egg_groups) = (&claimed_chains, &claimed_egg_groups);
+            scope.spawn(move || {
+                // Each worker only reads from the cache, so it gets a connection of its own
+                let conn = get_db_connection();
+                while !stop.load(Ordering::Relaxed)
+                    && let Some(name) = queue.lock().unwrap().next()
+                {
+                    let pokemon_data = api_get_pokemon_data(
+                        &response_fetcher(&conn, FetchMode::Online),
+                        &name,
+                        |chain| claimed_chains.lock().unwrap().insert(chain) && !is_evolution_chain_cached(&conn, chain).unwrap_or(false),
+                        |egg_group| {
+                            claimed_egg_groups.lock().unwrap().insert(egg_group.to_string())
+                                && !is_egg_group_cached(&conn, egg_group).unwrap_or(false)
+                        },
+                    );
+                    if sender.send((name, pokemon_data)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        for (name, pokemon_data) in receiver {
+            progress.set_message(name.clone());
+            match pokemon_data {
+                // Two names may resolve to the same pokemon, e.g. a species and its default form
+                Ok(Some(pokemon_data)) if is_species_cached(conn, &pokemon_data.form.pokemon) => summary.skipped += 1,
+                Ok(Some(pokemon_data)) => {
+                    if let Err(e) = cache_pokemon_data(conn, &pokemon_data) {
+                        stop.store(true, Ordering::Relaxed);
+                        return Err(e);
+                    }
+                    summary.fetched += 1;
+                }
+                Ok(None) => summary.missing.push(name),
+                Err(e) => {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+            progress.inc(1);
+        }
+        Ok(())
+    });
+
+    progress.finish_and_clear();
+    result?;
+    Ok(summary)
+}
@@ -1,28 +1,55 @@
+use crate::abilities::check_hidden_ability;
+use crate::api::move_data::PokeMoveData;
+use crate::api::species::PokeSpecies;
 use crate::api::pokemon_move::MoveLearnMethod;
-use crate::cache::{fetch_abilities, fetch_balls, fetch_move_methods, fetch_species_id, get_and_cache_pokemon, get_db_connection, is_species_cached};
-use crate::enums::{Gender, LearnMethod};
-use crate::errors::SpecErrors::{EvSumError, EvValueError, IllegalAbilityError, IvValueError, LevelTooLowMoveError, UnknownBallError, UnlearnableMoveError};
+use crate::balls::check_ball;
+use crate::breeding::{check_egg_moves, MAX_FATHER_FETCHES};
+use crate::cache::{fetch_abilities, fetch_balls, fetch_form, fetch_move_data, fetch_move_methods, fetch_species_details, get_and_cache_item, get_and_cache_move_data, get_db_connection, refresh_pokemon, resolve_pokemon};
+use crate::enums::{Gender, Generation, LearnMethod, PokeType, TeraType};
+use crate::errors::SpecErrors::{DuplicateMoveError, EvSumError, EvValueError, FormGenerationError, IllegalAbilityError, IvValueError, LevelTooLowMoveError, MoveGenerationError, MoveNotInGenerationError, TooManyMovesError, UnknownBallError, UnknownItemError, UnknownMoveError, UnlearnableMoveError};
 use crate::errors::{SpecError, SpecErrors};
+use crate::events::event_move_methods;
+use crate::evolution::{inherited_move_methods, pre_evolutions};
+use crate::hidden_power::{has_hidden_power, hidden_power};
+use crate::items::check_item;
+use crate::mechanics::check_mechanics;
+use crate::moveset::{check_pp_ups, generate_moveset, MovesetStrategy, SpecMove, MAX_MOVES};
+use crate::console::err;
+use crate::names::{display_name, to_ball_slug, to_slug};
+use crate::spread::{fill_spread, SpreadStrategy};
 use crate::util::sample_hash_set;
-use inflector::Inflector;
+use itertools::Itertools;
 use miette::{Error, Result};
+use rusqlite::Connection;
 use rand::{rng, Rng};
 use rusqlite::fallible_iterator::FallibleIterator;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
-static STAT_NAMES: [&str; 6] = ["atk", "def", "spatk", "spdef", "spd", "hp"];
+pub static STAT_NAMES: [&str; 6] = ["atk", "def", "spatk", "spdef", "spd", "hp"];
 pub static NATURES: [&str; 25] = [
     "Hardy", "Lonely", "Adamant", "Naughty", "Brave", "Bold", "Docile", "Impish", "Lax", "Relaxed",
     "Modest", "Mild", "Bashful", "Rash", "Quiet", "Calm", "Gentle", "Careful", "Quirky", "Sassy",
     "Timid", "Hasty", "Jolly", "Naive", "Serious",
 ];
 
+/// For a given nature, find the stat it raises and the stat it lowers, in that order.
+///
+/// `NATURES` is laid out in rows of five, where the row is the raised stat and the column is the lowered stat.
+/// Neutral natures raise and lower the same stat.
+pub fn nature_effect(nature: &str) -> Option<(&'static str, &'static str)> {
+    let idx = NATURES.iter().position(|n| n.eq_ignore_ascii_case(nature))?;
+    Some((STAT_NAMES[idx / 5], STAT_NAMES[idx % 5]))
+}
+
+#[derive(Clone)]
 pub struct StatSpreadBuilder {
     stat_max: u16,
     sum_max: u16,
     stats: HashMap<String, u16>,
     stat_type: Option<StatSpreadType>,
+    strategy: SpreadStrategy,
+    nature: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -38,6 +65,8 @@ impl StatSpreadBuilder {
             sum_max,
             stats: HashMap::new(),
             stat_type: None,
+            strategy: SpreadStrategy::Zero,
+            nature: None,
         }
     }
 
@@ -47,6 +76,8 @@ impl StatSpreadBuilder {
             sum_max: 31 * 6,
             stats: HashMap::new(),
             stat_type: Some(StatSpreadType::IV),
+            strategy: SpreadStrategy::Random,
+            nature: None,
         }
     }
 
@@ -56,6 +87,8 @@ impl StatSpreadBuilder {
             sum_max: 510,
             stats: HashMap::new(),
             stat_type: Some(StatSpreadType::EV),
+            strategy: SpreadStrategy::Random,
+            nature: None,
         }
     }
 
@@ -104,12 +137,26 @@ impl StatSpreadBuilder {
         self
     }
 
+    /// Set the strategy used to fill any stats that were not pinned
+    pub fn strategy(&mut self, strategy: SpreadStrategy) -> &mut Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the nature that nature-aware strategies (e.g. competitive EVs) should align to
+    pub fn nature(&mut self, nature: &str) -> &mut Self {
+        self.nature = Some(nature.to_string());
+        self
+    }
+
     pub fn build(&self) -> Result<StatSpread, SpecError> {
         StatSpread::new(
             self.stat_max,
             self.sum_max,
             self.stats.clone(),
             self.stat_type.clone().unwrap(),
+            &self.strategy,
+            self.nature.as_deref(),
         )
     }
 }
@@ -128,6 +175,8 @@ impl StatSpread {
         sum_max: u16,
         user_stats: HashMap<String, u16>,
         stat_type: StatSpreadType,
+        strategy: &SpreadStrategy,
+        nature: Option<&str>,
     ) -> Result<Self, SpecError> {
         let available_stats: HashSet<String> =
             HashSet::from(STAT_NAMES.clone().map(|x| x.to_string()));
@@ -161,14 +210,19 @@ impl StatSpread {
                     break;
                 }
 
-                sum = sum + value;
+                sum += value;
                 _stats.insert(stat.clone(), value);
-            } else {
-                _stats.insert(stat.clone(), 0);
             }
         }
 
-        if errors.len() > 0 {
+        // Only fill the remaining stats once the pinned ones are known to be legal
+        if errors.is_empty()
+            && let Err(e) = fill_spread(&mut _stats, stat_max, sum_max, &stat_type, strategy, nature)
+        {
+            errors.push(e);
+        }
+
+        if !errors.is_empty() {
             return Err(SpecError { causes: errors });
         }
 
@@ -181,6 +235,13 @@ impl StatSpread {
     }
 }
 
+impl StatSpread {
+    /// The value of every stat in the spread, keyed by stat name
+    pub fn stats(&self) -> &HashMap<String, u16> {
+        &self.stats
+    }
+}
+
 impl Display for StatSpread {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -199,7 +260,9 @@ impl Display for StatSpread {
 #[derive(Clone, Debug)]
 pub struct PokeSpec {
     species: String,
+    form: Option<String>, // The specific form (e.g. `raichu-alola`), if it isn't the species' usual one
     ability: String,
+    hidden_ability: bool,
     level: u8, // Max of 100
     nickname: Option<String>,
     shiny: bool,
@@ -208,53 +271,58 @@ pub struct PokeSpec {
     sid: usize,
     gender: Gender,
     ball: String,
+    item: Option<String>,
     nature: String,
     ivs: StatSpread, // Max of 31 per stat, no actual stat total
     evs: StatSpread, // Max of 252 per stat, with a total of 510
-    move_set: HashSet<String>,
+    move_set: Vec<SpecMove>, // In slot order, as each move was in the spec's generation
+    generation: Option<Generation>,
+    hidden_power: Option<(PokeType, u8)>, // Type and base power, if Hidden Power is in the move set
+    tera_type: Option<TeraType>, // Gen 9 only
+    dynamax_level: Option<u8>,   // Gen 8 only. Max of 10
+    gigantamax: bool,            // Gen 8 only
 }
 
-impl PokeSpec {
-    pub fn new(
-        species: String,
-        ability: String,
-        level: u8,
-        nickname: Option<String>,
-        shiny: bool,
-        ot: String,
-        tid: usize,
-        sid: usize,
-        gender: Gender,
-        ball: String,
-        nature: String,
-        ivs: StatSpread,
-        evs: StatSpread,
-        move_set: HashSet<String>
-    ) -> Self {
-
-        PokeSpec {
-            species,
-            ability,
-            level,
-            nickname,
-            shiny,
-            ot,
-            tid,
-            sid,
-            gender,
-            ball,
-            nature,
-            ivs,
-            evs,
-            move_set,
+/// Summarize a move for display, e.g. `Flamethrower: Fire, Special, 90 power, 100% accuracy, 24 PP (3 PP Ups)`
+fn format_move(spec_move: &SpecMove) -> String {
+    let move_data = &spec_move.data;
+    let mut details: Vec<String> = Vec::new();
+    if let Some(move_type) = move_data.move_type {
+        details.push(move_type.to_string());
+    }
+    if let Some(damage_class) = move_data.damage_class {
+        details.push(damage_class.to_string());
+    }
+    if let Some(power) = move_data.power {
+        details.push(format!("{power} power"));
+    }
+    if let Some(accuracy) = move_data.accuracy {
+        details.push(format!("{accuracy}% accuracy"));
+    }
+    if let Some(max_pp) = spec_move.max_pp() {
+        match spec_move.pp_ups {
+            0 => details.push(format!("{max_pp} PP")),
+            1 => details.push(format!("{max_pp} PP (1 PP Up)")),
+            pp_ups => details.push(format!("{max_pp} PP ({pp_ups} PP Ups)")),
         }
     }
+    if move_data.priority != 0 {
+        details.push(format!("{:+} priority", move_data.priority));
+    }
+
+    format!("{}: {}", display_name(&move_data.name), details.join(", "))
 }
 
 impl Display for PokeSpec {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let buffer: Vec<String> = vec![
-            self.species.clone(), String::from(" ("), if self.nickname.is_some() { self.nickname.clone().unwrap() } else { self.species.clone() }, String::from("): lvl. "), self.level.to_string(),
+            display_name(&self.species), String::from(" ("), if self.nickname.is_some() { self.nickname.clone().unwrap() } else { display_name(&self.species) }, String::from("): lvl. "), self.level.to_string(),
+            "\n".to_string(),
+            match &self.form {
+                Some(form) => format!("\tform: {}\n", display_name(form)),
+                None => String::new(),
+            },
+            String::from("\tability: "), display_name(&self.ability), String::from(if self.hidden_ability { " (hidden)" } else { "" }),
             "\n".to_string(),
             String::from("\tot: "), self.ot.to_string(),
             "\n".to_string(),
@@ -262,14 +330,35 @@ impl Display for PokeSpec {
             "\n".to_string(),
             String::from("\tsid: "), self.sid.to_string(),
             "\n".to_string(),
+            match &self.generation {
+                Some(generation) => format!("\tgen: {}\n", Into::<String>::into(generation.clone())),
+                None => String::new(),
+            },
             String::from("\tShiny: "), self.shiny.to_string(),
             "\n".to_string(),
-            String::from("\tball: "), self.ball.to_string().to_title_case(),
+            String::from("\tball: "), display_name(&self.ball),
             "\n".to_string(),
+            match &self.item {
+                Some(item) => format!("\titem: {}\n", display_name(item)),
+                None => String::new(),
+            },
             "\tMoves:".to_string(),
             "\n".to_string(),
-            self.move_set.clone().into_iter().map(|m| String::from("\t\t- ") + m.to_title_case().as_str()).collect::<Vec<String>>().join("\n"),
+            self.move_set.iter().map(|m| String::from("\t\t- ") + format_move(m).as_str()).collect::<Vec<String>>().join("\n"),
             "\n".to_string(),
+            match self.hidden_power {
+                Some((hp_type, power)) => format!("\tHidden Power: {hp_type} ({power})\n"),
+                None => String::new(),
+            },
+            match &self.tera_type {
+                Some(tera_type) => format!("\tTera Type: {tera_type}\n"),
+                None => String::new(),
+            },
+            match &self.dynamax_level {
+                Some(dynamax_level) => format!("\tDynamax Level: {dynamax_level}\n"),
+                None => String::new(),
+            },
+            if self.gigantamax { String::from("\tGigantamax: true\n") } else { String::new() },
             String::from("\tNature: "), self.nature.to_string(),
             "\n".to_string(),
             String::from("\tIVs: "), self.ivs.to_string(),
@@ -288,6 +377,7 @@ impl Display for PokeSpec {
 
 pub struct PokeSpecBuilder {
     species: String,
+    form: Option<String>, // e.g. `alola` for `raichu-alola`. None uses the species' default form
     ability: Option<String>,
     level: u8, // Max of 100
     nickname: Option<String>,
@@ -297,21 +387,35 @@ pub struct PokeSpecBuilder {
     sid: usize,
     gender: Option<Gender>,
     ball: String,
+    item: Option<String>,
     nature: Option<String>,
     ivs: StatSpreadBuilder, // Max of 31 per stat, no actual stat total
     evs: StatSpreadBuilder, // Max of 252 per stat, with a total of 510
-    move_set: HashSet<String>, // A set of up to four moves denoting the active move set of the pokemon
+    move_set: Vec<String>, // Up to four moves denoting the active move set of the pokemon, in slot order
+    moveset_strategy: MovesetStrategy, // How to fill the move set if no moves were given
+    pp_ups: Vec<u8>, // PP Ups for each move, in slot order. Moves without a count have none.
+    generation: Option<Generation>, // The generation the spec must be legal in. None assumes the latest.
+    game: Option<String>, // The version group the spec must be legal in, for mechanics that differ within a generation
+    hidden_power: Option<PokeType>, // Constrain the IVs to produce this Hidden Power type
+    tera_type: Option<TeraType>,
+    dynamax_level: Option<u8>,
+    gigantamax: bool,
 }
 
 impl PokeSpecBuilder {
 
     pub fn species(&mut self, species: &str) -> &mut Self {
-        self.species = species.to_string();
+        self.species = to_slug(species);
+        self
+    }
+
+    pub fn form(&mut self, form: &str) -> &mut Self {
+        self.form = Some(to_slug(form));
         self
     }
 
     pub fn ability(&mut self, ability: &str) -> &mut Self {
-        self.ability = Some(ability.to_string());
+        self.ability = Some(to_slug(ability));
         self
     }
 
@@ -351,7 +455,12 @@ impl PokeSpecBuilder {
     }
 
     pub fn ball(&mut self, ball: &str) -> &mut Self {
-        self.ball = ball.to_string();
+        self.ball = to_ball_slug(ball);
+        self
+    }
+
+    pub fn item(&mut self, item: &str) -> &mut Self {
+        self.item = Some(to_slug(item));
         self
     }
 
@@ -368,14 +477,55 @@ impl PokeSpecBuilder {
         &mut self.evs
     }
 
-    pub fn move_set(&mut self, move_set: HashSet<String>) -> &mut Self {
-        self.move_set = move_set;
+    pub fn move_set(&mut self, move_set: Vec<String>) -> &mut Self {
+        self.move_set = move_set.iter().map(|pk_move| to_slug(pk_move)).collect();
+        self
+    }
+
+    pub fn moveset_strategy(&mut self, moveset_strategy: MovesetStrategy) -> &mut Self {
+        self.moveset_strategy = moveset_strategy;
+        self
+    }
+
+    pub fn pp_ups(&mut self, pp_ups: Vec<u8>) -> &mut Self {
+        self.pp_ups = pp_ups;
+        self
+    }
+
+    pub fn generation(&mut self, generation: Generation) -> &mut Self {
+        self.generation = Some(generation);
+        self
+    }
+
+    pub fn game(&mut self, game: &str) -> &mut Self {
+        self.game = Some(to_slug(game));
+        self
+    }
+
+    pub fn hidden_power(&mut self, hp_type: PokeType) -> &mut Self {
+        self.hidden_power = Some(hp_type);
+        self
+    }
+
+    pub fn tera_type(&mut self, tera_type: TeraType) -> &mut Self {
+        self.tera_type = Some(tera_type);
+        self
+    }
+
+    pub fn dynamax_level(&mut self, dynamax_level: u8) -> &mut Self {
+        self.dynamax_level = Some(dynamax_level);
+        self
+    }
+
+    pub fn gigantamax(&mut self, gigantamax: bool) -> &mut Self {
+        self.gigantamax = gigantamax;
         self
     }
 
     pub fn new(species: &str) -> Self {
         PokeSpecBuilder {
-            species: species.to_string(),
+            species: to_slug(species),
+            form: None,
             ability: None, // Either get from user or fill randomly from DB
             level: 1,
             nickname: None,
@@ -384,57 +534,97 @@ impl PokeSpecBuilder {
             tid: 0,       // TODO: Implement
             sid: 0,       // TODO: Implement
             gender: None, // Either get from user or fill randomly from DB,
-            ball: "Poke".to_string(),
+            ball: "poke-ball".to_string(),
+            item: None,
             nature: None, // Either get from user or fill randomly from array
             ivs: StatSpreadBuilder::ivs(),
             evs: StatSpreadBuilder::evs(),
-            move_set: HashSet::new(),
+            move_set: Vec::new(),
+            moveset_strategy: MovesetStrategy::LevelUp,
+            pp_ups: Vec::new(),
+            generation: None,
+            game: None,
+            hidden_power: None,
+            tera_type: None,
+            dynamax_level: None,
+            gigantamax: false,
         }
     }
 
     pub fn build(&self) -> Result<PokeSpec, Error> {
         let conn = get_db_connection();
-        if !is_species_cached(&conn, self.species.as_str()) {
-            get_and_cache_pokemon(self.species.as_str())?;
-        }
-
-        let species_id = fetch_species_id(&conn, self.species.as_str())?;
+        let (pokemon, species_id) = resolve_pokemon(&conn, &self.species, self.form.as_deref())?;
+
+        // Pokemon cached before forms and species details were tracked would skip the checks that need them, e.g. on
+        // the ball, so fetch them again. If that fails, e.g. offline, say which checks were skipped.
+        let has_species_details = match fetch_form(&conn, species_id)? {
+            Some(form) => fetch_species_details(&conn, &form.species)?.is_some(),
+            None => false,
+        };
+        let (pokemon, species_id) = match has_species_details {
+            true => (pokemon, species_id),
+            false => refresh_pokemon(&conn, &pokemon).unwrap_or_else(|_| {
+                err(format!(
+                    "{} was cached before its species details were tracked, so its ball can't be fully checked. \
+                    Use `cache refresh` to fetch it again.",
+                    display_name(&pokemon)
+                )
+                .as_str());
+                (pokemon, species_id)
+            }),
+        };
 
         let mut error: Option<SpecError> = None;
 
+        // Determine whether the form existed in the target generation, e.g. no Alolan forms before Gen 7
+        let form = fetch_form(&conn, species_id)?;
+        if let (Some(generation), Some(form)) = (&self.generation, &form)
+            && form.generation.as_ref().is_some_and(|introduced| introduced > generation)
+        {
+            error = Some(SpecError { causes: vec![FormGenerationError {
+                pokemon: pokemon.clone(),
+                generation: Into::<String>::into(generation.clone()),
+            }] } + error);
+        }
+
         let legal_abilities = fetch_abilities(&conn, species_id)?;
 
         // Determine legality of the ability. If no ability was provided by the user, randomly select one instead.
-        match self.ability.clone() {
-            Some(ability) => {
-                if !legal_abilities.contains(&ability) {
+        if let Some(ability) = self.ability.clone() {
+            match legal_abilities.iter().find(|legal| legal.name == ability) {
+                Some(legal) if legal.is_hidden => {
+                    let hidden_errors = check_hidden_ability(&ability, self.generation.as_ref(), &self.ball);
+                    if !hidden_errors.is_empty() {
+                        error = Some(SpecError { causes: hidden_errors } + error);
+                    }
+                }
+                Some(_) => {}
+                None => {
                     if error.is_none() {
                         error = Some(SpecError {causes: Vec::new()})
                     }
-                    error = Some(error.unwrap() + IllegalAbilityError {species: self.species.clone(), ability: ability.clone()});
+                    error = Some(error.unwrap() + IllegalAbilityError {species: pokemon.clone(), ability: ability.clone()});
                 }
             }
-
-            None => {}
         }
 
+        let species_details = match &form {
+            Some(form) => fetch_species_details(&conn, &form.species)?,
+            None => None,
+        };
+
         // Determine legality of the moveset
-        for poke_move in &self.move_set {
-            let methods = fetch_move_methods(&conn, species_id, poke_move);
-            if methods.is_ok() {
-                match is_learnable_move(&self.species, poke_move, self.level, &methods?) {
-                    Err(e) => {
-                        if error.is_none() {
-                            error = Some(SpecError {causes: Vec::new()});
-                        }
-
-                        error = Some(error.unwrap() + e);
-                    },
-                    _ => {}
-                }
-            } else {
-                println!("{:?}", methods?)
-            }
+        let owner = MovesetOwner { pokemon: &pokemon, species_id, species_details: species_details.as_ref() };
+        let move_errors = check_moveset(
+            &conn,
+            &owner,
+            &self.move_set,
+            self.level,
+            self.generation.as_ref(),
+            false,
+        )?;
+        if !move_errors.is_empty() {
+            error = Some(SpecError { causes: move_errors } + error);
         }
 
         let balls = fetch_balls(&conn)?;
@@ -444,8 +634,69 @@ impl PokeSpecBuilder {
             }
 
             error = Some(error.unwrap() + UnknownBallError {ball: self.ball.clone()});
+        } else {
+            let ball_errors = check_ball(&pokemon, species_details.as_ref(), &self.ball, self.generation.as_ref());
+            if !ball_errors.is_empty() {
+                error = Some(SpecError { causes: ball_errors } + error);
+            }
         }
 
+        // Determine the legality of the held item, if there is one
+        if let Some(item) = &self.item {
+            let item_errors = match get_and_cache_item(&conn, &item.to_lowercase())? {
+                Some(poke_item) => check_item(&pokemon, &poke_item, self.generation.as_ref()),
+                None => vec![UnknownItemError { item: item.clone() }],
+            };
+
+            if !item_errors.is_empty() {
+                error = Some(SpecError { causes: item_errors } + error);
+            }
+        }
+
+        // Determine the legality of Terastallization, Dynamax, and Gigantamax for the target generation
+        let mechanic_errors = check_mechanics(
+            &pokemon,
+            self.generation.as_ref(),
+            self.game.as_deref(),
+            self.tera_type.as_ref(),
+            self.dynamax_level,
+            self.gigantamax,
+        );
+        if !mechanic_errors.is_empty() {
+            error = Some(SpecError { causes: mechanic_errors } + error);
+        }
+
+        // Moves drawn from the learnset are legal by construction, so they don't need checking
+        let move_set = if self.move_set.is_empty() {
+            generate_moveset(&conn, species_id, self.level, self.generation.as_ref(), self.moveset_strategy)?
+        } else {
+            self.move_set.clone()
+        };
+
+        // Show each move as it was in the target generation
+        let mut move_data: Vec<PokeMoveData> = Vec::new();
+        for pk_move in &move_set {
+            if let Some(data) = get_and_cache_move_data(&conn, pk_move)? {
+                move_data.push(match &self.generation {
+                    Some(generation) => data.in_generation(generation),
+                    None => data,
+                });
+            }
+        }
+
+        // PP Ups can only be matched to moves once every move is known to exist
+        if move_data.len() == move_set.len() {
+            let pp_up_errors = check_pp_ups(&move_data, &self.pp_ups, self.generation.as_ref());
+            if !pp_up_errors.is_empty() {
+                error = Some(SpecError { causes: pp_up_errors } + error);
+            }
+        }
+        let move_set: Vec<SpecMove> = move_data
+            .into_iter()
+            .enumerate()
+            .map(|(slot, data)| SpecMove { data, pp_ups: self.pp_ups.get(slot).copied().unwrap_or(0) })
+            .collect();
+
         // Determine the legality of the provided gender. If no gender was provided, select one randomly
         if self.gender.is_some() {
             // TODO: Check legality
@@ -453,13 +704,23 @@ impl PokeSpecBuilder {
 
         }
 
+        // Select the nature before the spreads, as EV strategies may be aligned to it
+        let nature = self.nature.clone().unwrap_or(NATURES.get(rng().random_range(0..NATURES.len())).unwrap().to_string());
+
+        // Hidden Power was last IV-dependent in Gen 7, so assume its rules when no generation was given
+        let hp_generation = self.generation.clone().unwrap_or(Generation::GEN7);
+        let mut iv_builder = self.ivs.clone();
+        if let Some(hp_type) = self.hidden_power {
+            iv_builder.strategy(SpreadStrategy::HiddenPower(hp_type, hp_generation.clone()));
+        }
+
         // Check if IVs or EVs have any errors. If so, accumulate them in the `error` field.
-        let ivs = self.ivs.build();
+        let ivs = iv_builder.build();
         if ivs.is_err() {
             error = Some(ivs.clone().err().unwrap() + error);
         }
 
-        let evs = self.evs.build();
+        let evs = self.evs.clone().nature(&nature).build();
         if evs.is_err() {
             error = Some(evs.clone().err().unwrap() + error);
         }
@@ -468,72 +729,244 @@ impl PokeSpecBuilder {
             return Err(error.unwrap())?;
         }
 
-        Ok(PokeSpec::new(
-            self.species.clone(),
-            self.ability.clone().unwrap_or(sample_hash_set(&fetch_abilities(&conn, species_id)?)),
-            self.level,
-            self.nickname.clone(),
-            self.shiny,
-            self.ot.clone(),
-            self.tid,
-            self.sid,
-            self.gender.clone().unwrap_or(Gender::Genderless),
-            self.ball.clone(),
-            self.nature.clone().unwrap_or(NATURES.get(rng().random_range(0..NATURES.len())).unwrap().to_string()),
-            ivs?,
-            evs?,
-            self.move_set.clone(),
-        ))
+        let ivs = ivs?;
+
+        // Randomly selected abilities are never hidden, as hidden abilities are rare in the games
+        let ability = match self.ability.clone() {
+            Some(ability) => ability,
+            None => {
+                let regular: HashSet<String> = legal_abilities.iter().filter(|a| !a.is_hidden).map(|a| a.name.clone()).collect();
+                if regular.is_empty() {
+                    sample_hash_set(&legal_abilities.iter().map(|a| a.name.clone()).collect())
+                } else {
+                    sample_hash_set(&regular)
+                }
+            }
+        };
+        let hidden_ability = legal_abilities.iter().any(|legal| legal.name == ability && legal.is_hidden);
+
+        let hidden_power = if move_set.iter().any(|pk_move| pk_move.data.name == "hidden-power") && has_hidden_power(&hp_generation) {
+            Some(hidden_power(ivs.stats(), &hp_generation))
+        } else {
+            None
+        };
+
+        Ok(PokeSpec {
+            species: self.species.clone(),
+            form: if pokemon.eq_ignore_ascii_case(&self.species) { None } else { Some(pokemon.clone()) },
+            ability,
+            hidden_ability,
+            level: self.level,
+            nickname: self.nickname.clone(),
+            shiny: self.shiny,
+            ot: self.ot.clone(),
+            tid: self.tid,
+            sid: self.sid,
+            gender: self.gender.clone().unwrap_or(Gender::Genderless),
+            ball: self.ball.clone(),
+            item: self.item.clone(),
+            nature,
+            ivs,
+            evs: evs?,
+            move_set,
+            generation: self.generation.clone(),
+            hidden_power,
+            tera_type: self.tera_type,
+            dynamax_level: self.dynamax_level,
+            gigantamax: self.gigantamax,
+        })
+
+
+    }
+}
+
+/// The cached pokemon a moveset is checked for
+#[derive(Clone, Copy)]
+pub(crate) struct MovesetOwner<'a> {
+    pub pokemon: &'a str,
+    pub species_id: i32,
+    pub species_details: Option<&'a PokeSpecies>, // `None` for species cached before breeding data was tracked
+}
 
+/// Check that a pokemon could know every move of a moveset at its level, in the target generation.
+///
+/// Moves may also have been learned as a pre-evolution or from an event, and egg moves need a chain of fathers
+/// to pass them down. Species cached before breeding data was tracked skip the breeding checks.
+///
+/// With `cache_only`, nothing is fetched from PokeAPI: only cached pre-evolutions and fathers count,
+/// and moves whose details aren't cached aren't checked against the generation they were introduced in.
+pub(crate) fn check_moveset(
+    conn: &Connection,
+    owner: &MovesetOwner,
+    move_set: &[String],
+    level: u8,
+    generation: Option<&Generation>,
+    cache_only: bool,
+) -> Result<Vec<SpecErrors>> {
+    let MovesetOwner { pokemon, species_id, species_details } = *owner;
+    let mut errors: Vec<SpecErrors> = Vec::new();
+
+    // Moves may also have been learned as a pre-evolution, before evolving
+    let pre_evolutions = match species_details {
+        Some(species_details) => pre_evolutions(conn, species_details, cache_only)?,
+        None => Vec::new(),
+    };
+
+    if move_set.len() > MAX_MOVES {
+        errors.push(TooManyMovesError { count: move_set.len().to_string() });
+    }
+    for poke_move in move_set.iter().duplicates() {
+        errors.push(DuplicateMoveError { pk_move: poke_move.clone() });
+    }
+
+    // Egg moves are grouped by the stage that hatched knowing them
+    let mut egg_moves: HashMap<i32, Vec<String>> = HashMap::new();
+    for poke_move in move_set.iter().unique() {
+        // The move itself must exist in the target generation before any pokemon can learn it
+        let move_data = if cache_only {
+            fetch_move_data(conn, poke_move)?
+        } else {
+            get_and_cache_move_data(conn, poke_move)?
+        };
+        match move_data {
+            None if cache_only => {}
+            None => {
+                errors.push(UnknownMoveError { pk_move: poke_move.clone() });
+                continue;
+            }
+            Some(move_data) => {
+                if let (Some(generation), Some(introduced)) = (generation, &move_data.generation)
+                    && introduced > generation
+                {
+                    errors.push(MoveGenerationError {
+                        pk_move: poke_move.clone(),
+                        generation: generation.clone().into(),
+                    });
+                    continue;
+                }
+            }
+        }
+
+        let mut methods = fetch_move_methods(conn, species_id, poke_move)?;
+        methods.extend(event_move_methods(pokemon, poke_move));
+
+        let mut stage_methods = vec![(species_id, methods)];
+        for pre_evolution in &pre_evolutions {
+            stage_methods.push((pre_evolution.pokemon_id, inherited_move_methods(conn, pre_evolution, poke_move)?));
+        }
+        let all_methods: HashSet<MoveLearnMethod> =
+            stage_methods.iter().flat_map(|(_, methods)| methods.iter().cloned()).collect();
+
+        match is_learnable_move(pokemon, poke_move, level, &all_methods, generation) {
+            Err(e) => errors.push(e),
+            Ok(LearnMethod::Egg) => {
+                // The nearest stage that learns it as an egg move is the one it hatched as
+                if let Some((stage_id, _)) = stage_methods
+                    .iter()
+                    .find(|(_, methods)| is_learnable_move(pokemon, poke_move, level, methods, generation).is_ok())
+                {
+                    egg_moves.entry(*stage_id).or_default().push(poke_move.clone());
+                }
+            }
+            _ => {}
+        }
+    }
 
+    // Egg moves also need a chain of fathers to pass them down
+    for (stage_id, stage_egg_moves) in &egg_moves {
+        let stage_details = if *stage_id == species_id {
+            species_details.cloned()
+        } else {
+            pre_evolutions
+                .iter()
+                .find(|pre_evolution| pre_evolution.pokemon_id == *stage_id)
+                .map(|pre_evolution| pre_evolution.species.clone())
+        };
+
+        if let Some(stage_details) = &stage_details
+            && stage_details.evolution_chain.is_some()
+        {
+            let fetch_limit = if cache_only { 0 } else { MAX_FATHER_FETCHES };
+            errors.extend(check_egg_moves(conn, stage_details, *stage_id, stage_egg_moves, generation, fetch_limit)?);
+        }
     }
+
+    Ok(errors)
 }
 
+/// Check that a species can learn a move at its level, returning the method it learns it by.
+///
+/// Methods that don't depend on breeding are preferred, as egg moves also need a breeding chain (see `check_egg_moves`).
+/// If a generation was given, only methods from games the pokemon can be transferred from into that generation count,
+/// and egg moves only count if they are egg moves in that generation itself, where the breeding happens.
 pub fn is_learnable_move(
     species: &str,
     pk_move: &str,
     pk_level: u8,
     methods: &HashSet<MoveLearnMethod>,
-) -> Result<bool, SpecErrors> {
-    // No methods mean the move is not learnable at all
-    if methods.len() < 1 {
-        return Err(UnlearnableMoveError {
+    generation: Option<&Generation>,
+) -> Result<LearnMethod, SpecErrors> {
+    let usable: Vec<&MoveLearnMethod> = methods
+        .iter()
+        .filter(|method| match (generation, &method.method) {
+            (None, _) => true,
+            (Some(generation), LearnMethod::Egg) => method.generation == *generation,
+            (Some(generation), _) => method.generation.can_transfer_to(generation),
+        })
+        .collect();
+    if let Some(generation) = generation
+        && usable.is_empty()
+        && !methods.is_empty()
+    {
+        return Err(MoveNotInGenerationError {
             species: String::from(species),
             pk_move: String::from(pk_move),
-        })
+            generation: generation.clone().into(),
+        });
     }
 
     let mut min_learn_level: Option<u8> = None;
-    for method in methods {
-        // Alternative learn methods mean the move is learnable regardless of level
-        if [LearnMethod::Egg, LearnMethod::Machine, LearnMethod::Tutor].contains(&method.method) {
-            return Ok(true);
-        }
-
-        // Level-based learning must work number-wise, else move can't be learned at all
-        if method.method == LearnMethod::LevelUp {
-            let method_level = method.level_learned_at.clone().unwrap();
-            if method_level <= pk_level {
-                return Ok(true);
-            }
-
-            if min_learn_level == None {
-                min_learn_level = Some(method_level);
-            } else {
-                let lvl = min_learn_level.unwrap();
-                if method_level < lvl {
-                    min_learn_level = Some(method_level);
-                } else {
-                    min_learn_level = Some(lvl);
+    let mut is_egg_move = false;
+    for method in usable {
+        match method.method {
+            // Alternative learn methods mean the move is learnable regardless of level
+            LearnMethod::Machine
+            | LearnMethod::Tutor
+            | LearnMethod::StadiumSurfingPikachu
+            | LearnMethod::LightBallEgg
+            | LearnMethod::ColosseumPurification
+            | LearnMethod::XdShadow
+            | LearnMethod::XdPurification
+            | LearnMethod::FormChange
+            | LearnMethod::ZygardeCube => return Ok(method.method.clone()),
+            // Level-based learning must work number-wise, else move can't be learned at all.
+            // Event pokemon are distributed at a fixed level, so they work the same way.
+            LearnMethod::LevelUp | LearnMethod::Event => {
+                let method_level = method.level_learned_at.unwrap_or(0);
+                if method_level <= pk_level {
+                    return Ok(method.method.clone());
                 }
+                min_learn_level = Some(min_learn_level.map_or(method_level, |min| min.min(method_level)));
             }
+            LearnMethod::Egg => is_egg_move = true,
         }
     }
 
-    Err(LevelTooLowMoveError {
-        species: String::from(species),
-        pk_move: String::from(pk_move),
-        level: pk_level.to_string(),
-        min_level: min_learn_level.unwrap().to_string(),
-    })
+    if is_egg_move {
+        return Ok(LearnMethod::Egg);
+    }
+
+    match min_learn_level {
+        Some(min_level) => Err(LevelTooLowMoveError {
+            species: String::from(species),
+            pk_move: String::from(pk_move),
+            level: pk_level.to_string(),
+            min_level: min_level.to_string(),
+        }),
+        // No usable methods mean the move is not learnable at all
+        None => Err(UnlearnableMoveError {
+            species: String::from(species),
+            pk_move: String::from(pk_move),
+        }),
+    }
 }
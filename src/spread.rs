@@ -0,0 +1,218 @@
+use crate::enums::{Generation, PokeType};
+use crate::errors::SpecErrors;
+use crate::errors::SpecErrors::{IllegalSpreadStrategyError, PerfectIvError};
+use crate::hidden_power::fill_hidden_power;
+use crate::spec::{nature_effect, StatSpreadType, STAT_NAMES};
+use miette::miette;
+use rand::seq::SliceRandom;
+use rand::{rng, Rng};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// The EVs a competitive spread gives to its primary, secondary, and leftover stats
+const COMPETITIVE_EVS: [u16; 3] = [252, 252, 4];
+
+/// How the stats of a spread that were not pinned by the user get filled in.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SpreadStrategy {
+    /// Leave every unpinned stat at 0
+    Zero,
+    /// IVs: uniform in 0..=31. EVs: a random legal distribution whose sum does not exceed the cap.
+    Random,
+    /// IVs only: guarantee that at least N stats are 31, as with legendaries and raid dens.
+    Perfect(u8),
+    /// EVs only: 252/252/4, with the primary stats chosen according to the nature.
+    Competitive,
+    /// IVs only: the highest IVs that give Hidden Power the given type in the given generation.
+    HiddenPower(PokeType, Generation),
+}
+
+/// A basic string-to-enum conversion. Accepts `zero`, `random`, `competitive`, and `perfect-N` (or `perfectN`).
+impl TryFrom<&str> for SpreadStrategy {
+    type Error = miette::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.to_lowercase();
+        match value.as_str() {
+            "zero" => Ok(SpreadStrategy::Zero),
+            "random" => Ok(SpreadStrategy::Random),
+            "competitive" => Ok(SpreadStrategy::Competitive),
+            _ => match value.strip_prefix("perfect") {
+                Some(count) => match count.trim_start_matches('-').parse::<u8>() {
+                    Ok(count) if count <= 6 => Ok(SpreadStrategy::Perfect(count)),
+                    _ => Err(miette!(
+                        "'{}' is not a valid perfect IV count! Use perfect-0 through perfect-6.",
+                        value
+                    )),
+                },
+                None => Err(miette!("'{}' is not a valid spread strategy!", value)),
+            },
+        }
+    }
+}
+
+impl Display for SpreadStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpreadStrategy::Zero => write!(f, "zero"),
+            SpreadStrategy::Random => write!(f, "random"),
+            SpreadStrategy::Perfect(count) => write!(f, "perfect-{count}"),
+            SpreadStrategy::Competitive => write!(f, "competitive"),
+            SpreadStrategy::HiddenPower(hp_type, _) => {
+                write!(f, "hidden-power-{}", hp_type.to_string().to_lowercase())
+            }
+        }
+    }
+}
+
+/// Fill every stat in `stats` that the user did not pin, according to the given strategy.
+///
+/// `stats` must only contain the pinned stats, and `sum_max` is the budget shared by all six stats.
+pub fn fill_spread(
+    stats: &mut HashMap<String, u16>,
+    stat_max: u16,
+    sum_max: u16,
+    stat_type: &StatSpreadType,
+    strategy: &SpreadStrategy,
+    nature: Option<&str>,
+) -> Result<(), SpecErrors> {
+    let unpinned: Vec<&str> = STAT_NAMES
+        .iter()
+        .copied()
+        .filter(|stat| !stats.contains_key(*stat))
+        .collect();
+
+    match (strategy, stat_type) {
+        (SpreadStrategy::Zero, _) => fill_constant(stats, &unpinned, 0),
+        (SpreadStrategy::Random, StatSpreadType::IV) => fill_uniform(stats, &unpinned, stat_max),
+        (SpreadStrategy::Random, StatSpreadType::EV) => {
+            fill_random_budget(stats, &unpinned, stat_max, sum_max)
+        }
+        (SpreadStrategy::Perfect(count), StatSpreadType::IV) => {
+            fill_perfect(stats, &unpinned, stat_max, *count)?
+        }
+        (SpreadStrategy::Competitive, StatSpreadType::EV) => {
+            fill_competitive(stats, &unpinned, stat_max, sum_max, nature)
+        }
+        (SpreadStrategy::HiddenPower(hp_type, generation), StatSpreadType::IV) => {
+            fill_hidden_power(stats, &unpinned, stat_max, hp_type, generation)?
+        }
+        (strategy, stat_type) => {
+            return Err(IllegalSpreadStrategyError {
+                strategy: strategy.to_string(),
+                stat_type: format!("{stat_type:?}"),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn fill_constant(stats: &mut HashMap<String, u16>, unpinned: &[&str], value: u16) {
+    for stat in unpinned {
+        stats.insert(stat.to_string(), value);
+    }
+}
+
+fn fill_uniform(stats: &mut HashMap<String, u16>, unpinned: &[&str], stat_max: u16) {
+    let mut rng = rng();
+    for stat in unpinned {
+        stats.insert(stat.to_string(), rng.random_range(0..=stat_max));
+    }
+}
+
+/// Guarantee that `count` stats are maxed out, counting any pinned stats that already are, and roll the rest.
+fn fill_perfect(
+    stats: &mut HashMap<String, u16>,
+    unpinned: &[&str],
+    stat_max: u16,
+    count: u8,
+) -> Result<(), SpecErrors> {
+    let already_perfect = stats.values().filter(|value| **value == stat_max).count();
+    let needed = (count as usize).saturating_sub(already_perfect);
+
+    if needed > unpinned.len() {
+        return Err(PerfectIvError {
+            count: count.to_string(),
+            available: (already_perfect + unpinned.len()).to_string(),
+        });
+    }
+
+    let mut shuffled = unpinned.to_vec();
+    shuffled.shuffle(&mut rng());
+    let (perfect, rest) = shuffled.split_at(needed);
+
+    fill_constant(stats, perfect, stat_max);
+    fill_uniform(stats, rest, stat_max);
+    Ok(())
+}
+
+/// Hand out a random amount of the remaining budget, one point at a time, to the unpinned stats.
+fn fill_random_budget(
+    stats: &mut HashMap<String, u16>,
+    unpinned: &[&str],
+    stat_max: u16,
+    sum_max: u16,
+) {
+    let mut rng = rng();
+    let budget = sum_max.saturating_sub(stats.values().sum());
+    let mut total = if unpinned.is_empty() {
+        0
+    } else {
+        rng.random_range(0..=budget)
+    };
+
+    fill_constant(stats, unpinned, 0);
+    while total > 0 {
+        let open: Vec<&str> = unpinned
+            .iter()
+            .copied()
+            .filter(|stat| stats[*stat] < stat_max)
+            .collect();
+        if open.is_empty() {
+            break;
+        }
+
+        let stat = open[rng.random_range(0..open.len())];
+        *stats.get_mut(stat).unwrap() += 1;
+        total -= 1;
+    }
+}
+
+/// Pick the stats that receive the 252, 252, and 4 of a competitive spread, for a given nature.
+///
+/// Offensive natures pair their boosted stat with speed (or the attacking stat the nature doesn't lower, for
+/// speed natures). Defensive natures pair their boosted stat with HP. Neutral natures fall back to HP and speed.
+fn competitive_targets(nature: Option<&str>) -> [&'static str; 3] {
+    match nature.and_then(nature_effect) {
+        Some(("atk", lowered)) if lowered != "atk" => ["atk", "spd", "hp"],
+        Some(("spatk", lowered)) if lowered != "spatk" => ["spatk", "spd", "hp"],
+        Some(("spd", "atk")) => ["spd", "spatk", "hp"],
+        Some(("spd", lowered)) if lowered != "spd" => ["spd", "atk", "hp"],
+        Some(("def", lowered)) if lowered != "def" => ["def", "hp", "spdef"],
+        Some(("spdef", lowered)) if lowered != "spdef" => ["spdef", "hp", "def"],
+        _ => ["hp", "spd", "def"],
+    }
+}
+
+/// Fill the unpinned stats with a 252/252/4 spread aligned to the nature, without exceeding the budget.
+fn fill_competitive(
+    stats: &mut HashMap<String, u16>,
+    unpinned: &[&str],
+    stat_max: u16,
+    sum_max: u16,
+    nature: Option<&str>,
+) {
+    let mut budget = sum_max.saturating_sub(stats.values().sum());
+    fill_constant(stats, unpinned, 0);
+
+    for (target, evs) in competitive_targets(nature).into_iter().zip(COMPETITIVE_EVS) {
+        if !unpinned.contains(&target) {
+            continue;
+        }
+
+        let value = budget.min(stat_max).min(evs);
+        stats.insert(target.to_string(), value);
+        budget -= value;
+    }
+}
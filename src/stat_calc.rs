@@ -0,0 +1,55 @@
+use crate::spec::nature_effect;
+use std::collections::HashMap;
+
+/// The order in which in-game summary screens list stats
+pub static SUMMARY_STAT_ORDER: [&str; 6] = ["hp", "atk", "def", "spatk", "spdef", "spd"];
+
+/// A stat as observed on a summary screen at a given level
+#[derive(Clone, Debug)]
+pub struct StatSnapshot {
+    pub level: u8,
+    pub stats: HashMap<String, u16>,
+}
+
+/// The nature modifier for a given stat, as a percentage
+fn nature_modifier(stat: &str, nature: &str) -> u32 {
+    match nature_effect(nature) {
+        Some((raised, lowered)) if raised == lowered => 100,
+        Some((raised, _)) if raised == stat => 110,
+        Some((_, lowered)) if lowered == stat => 90,
+        _ => 100,
+    }
+}
+
+/// Calculate the value of a stat using the Gen 3+ formula
+pub fn calc_stat(stat: &str, base: u16, iv: u16, ev: u16, level: u8, nature: &str) -> u16 {
+    let level = level as u32;
+    let scaled = (2 * base as u32 + iv as u32 + ev as u32 / 4) * level / 100;
+
+    if stat == "hp" {
+        // Shedinja's HP is always 1
+        if base == 1 {
+            return 1;
+        }
+        return (scaled + level + 10) as u16;
+    }
+
+    ((scaled + 5) * nature_modifier(stat, nature) / 100) as u16
+}
+
+/// Find every IV that is consistent with all the observed values of a stat
+pub fn possible_ivs(
+    stat: &str,
+    base: u16,
+    ev: u16,
+    nature: &str,
+    snapshots: &[StatSnapshot],
+) -> Vec<u16> {
+    (0..=31)
+        .filter(|iv| {
+            snapshots
+                .iter()
+                .all(|snapshot| calc_stat(stat, base, *iv, ev, snapshot.level, nature) == snapshot.stats[stat])
+        })
+        .collect()
+}